@@ -0,0 +1,140 @@
+use crate::Wallet;
+use crate::WalletAccountInfo;
+use crate::WalletError;
+use crate::WalletResult;
+
+/// Management of every account which the app is authorized to use.
+///
+/// [`Wallet::wallet_account`] is the active account, which is used by every
+/// feature of the wallet. Wallets routinely authorize several accounts, and
+/// this trait allows the app to list them, switch the active account and run
+/// features against a specific account without changing the active one.
+///
+/// ```rust
+/// # #[cfg(feature = "solana")]
+/// # {
+/// use solana_keypair::Keypair;
+/// use wallet_standard::KeypairWallet;
+/// use wallet_standard::prelude::*;
+///
+/// # futures::executor::block_on(async {
+/// let mut wallet = KeypairWallet::builder()
+/// 	.keypairs(vec![Keypair::new(), Keypair::new()])
+/// 	.build();
+/// let accounts = wallet.connect().await?;
+/// let second = accounts[1].address();
+///
+/// // Sign with the second account while the first account stays active.
+/// let output = wallet
+/// 	.with_account(&second)?
+/// 	.sign_message_async("hello")
+/// 	.await?;
+/// assert!(
+/// 	output
+/// 		.signature()
+/// 		.verify(&accounts[1].public_key(), &output.signed_message())
+/// );
+/// assert_eq!(wallet.wallet_account(), Some(accounts[0].clone()));
+///
+/// wallet.select_account(&second)?;
+/// assert_eq!(wallet.wallet_account(), Some(accounts[1].clone()));
+/// # WalletResult::Ok(())
+/// # })
+/// # .unwrap();
+/// # }
+/// ```
+pub trait WalletAccounts: Wallet {
+	/// Every account which the app is authorized to use.
+	fn wallet_accounts(&self) -> Vec<Self::Account>;
+
+	/// Make the authorized account with the provided address the active
+	/// account.
+	///
+	/// # Errors
+	///
+	/// Returns [`WalletError::WalletAccountNotFound`] when the app isn't
+	/// authorized to use the account.
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account>;
+
+	/// Reconcile the active account with the latest [`Self::wallet_accounts`].
+	///
+	/// This should be called whenever the authorized accounts change. See
+	/// [`reconcile_wallet_account`] for the rules which are applied.
+	fn sync_wallet_account(&mut self) -> Option<Self::Account>;
+
+	/// Find the authorized account with the provided address.
+	fn find_account(&self, address: &str) -> WalletResult<Self::Account> {
+		self.wallet_accounts()
+			.into_iter()
+			.find(|account| account.address() == address)
+			.ok_or_else(|| WalletError::WalletAccountNotFound(address.to_string()))
+	}
+
+	/// A copy of the wallet with the provided account active.
+	///
+	/// Every feature called on the copy uses the account, while the active
+	/// account of `self` is left untouched.
+	fn with_account(&self, address: &str) -> WalletResult<Self>
+	where
+		Self: Clone + Sized,
+	{
+		let mut wallet = self.clone();
+		wallet.select_account(address)?;
+
+		Ok(wallet)
+	}
+}
+
+/// Determine the active account after the authorized accounts have changed.
+///
+/// - When the active account is still authorized, the latest version of the
+///   account is returned so changes to its chains, features or label are picked
+///   up.
+/// - When the active account has disappeared, the first remaining account
+///   becomes active, or `None` when no accounts are left.
+/// - When there is no active account, the wallet stays disconnected.
+pub fn reconcile_wallet_account<A: WalletAccountInfo + Clone>(
+	active: Option<&A>,
+	accounts: &[A],
+) -> Option<A> {
+	let active = active?;
+	let address = active.address();
+
+	accounts
+		.iter()
+		.find(|account| account.address() == address)
+		.or_else(|| accounts.first())
+		.cloned()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::WalletAccountSnapshot;
+
+	fn account(address: &str, label: &str) -> WalletAccountSnapshot {
+		WalletAccountSnapshot::builder()
+			.address(address)
+			.public_key(vec![])
+			.label(label)
+			.build()
+	}
+
+	#[test]
+	fn reconcile_keeps_refreshes_and_falls_back() {
+		let a = account("a", "A");
+		let b = account("b", "B");
+		let renamed = account("b", "Renamed");
+
+		assert_eq!(
+			reconcile_wallet_account(Some(&b), &[a.clone(), renamed.clone()]),
+			Some(renamed)
+		);
+		assert_eq!(
+			reconcile_wallet_account(Some(&b), std::slice::from_ref(&a)),
+			Some(a.clone())
+		);
+		assert_eq!(reconcile_wallet_account(Some(&b), &[]), None);
+		assert_eq!(reconcile_wallet_account(None, &[a, b]), None);
+	}
+}
@@ -0,0 +1,132 @@
+use std::future::Future;
+
+use crate::WalletError;
+use crate::WalletResult;
+
+/// The result of a batch which stops at the first failing item.
+pub type BatchResult<T> = Result<Vec<T>, BatchError<T>>;
+
+/// An item of a batch failed.
+///
+/// The items before `index` completed and their outputs are kept in
+/// `completed`. The items after `index` were never attempted.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("Item {index} of the batch failed: {error}")]
+pub struct BatchError<T> {
+	/// The index of the failing item.
+	pub index: usize,
+	/// The error of the failing item.
+	pub error: WalletError,
+	/// The outputs of the items which completed before the failure.
+	pub completed: Vec<T>,
+}
+
+impl<T> BatchError<T> {
+	/// The outputs of every item, with the failing item as an error.
+	pub fn into_results(self) -> Vec<WalletResult<T>> {
+		self.completed
+			.into_iter()
+			.map(Ok)
+			.chain(std::iter::once(Err(self.error)))
+			.collect()
+	}
+}
+
+impl<T> From<BatchError<T>> for WalletError {
+	fn from(value: BatchError<T>) -> Self {
+		value.error
+	}
+}
+
+/// Run `run` for each input in order, stopping at the first failure.
+///
+/// Items are run one at a time so a failure prevents the remaining items from
+/// being attempted, e.g. no further transactions are sent once one of them
+/// has been rejected.
+pub async fn try_batch<I, T, F, Fut>(inputs: Vec<I>, mut run: F) -> BatchResult<T>
+where
+	F: FnMut(I) -> Fut,
+	Fut: Future<Output = WalletResult<T>>,
+{
+	let mut completed = Vec::with_capacity(inputs.len());
+
+	for (index, input) in inputs.into_iter().enumerate() {
+		match run(input).await {
+			Ok(output) => completed.push(output),
+			Err(error) => {
+				return Err(BatchError {
+					index,
+					error,
+					completed,
+				});
+			}
+		}
+	}
+
+	Ok(completed)
+}
+
+/// Check that a wallet returned one output for each of the `expected` inputs.
+///
+/// # Errors
+///
+/// Returns [`WalletError::UnexpectedOutputCount`] when the number of outputs
+/// differs.
+pub fn expect_outputs<T>(expected: usize, outputs: Vec<T>) -> WalletResult<Vec<T>> {
+	if outputs.len() == expected {
+		Ok(outputs)
+	} else {
+		Err(WalletError::UnexpectedOutputCount {
+			expected,
+			received: outputs.len(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+
+	use super::*;
+
+	#[test]
+	fn batch_stops_at_the_first_failure() {
+		let mut attempted = vec![];
+		let result = block_on(try_batch(vec![1, 2, 3, 4], |input| {
+			attempted.push(input);
+
+			async move {
+				if input == 3 {
+					Err(WalletError::UserRejected)
+				} else {
+					Ok(input * 10)
+				}
+			}
+		}));
+
+		let error = result.unwrap_err();
+		assert_eq!(error.index, 2);
+		assert_eq!(error.completed, [10, 20]);
+		assert_eq!(
+			error.to_string(),
+			"Item 2 of the batch failed: The user rejected the request"
+		);
+		assert_eq!(attempted, [1, 2, 3]);
+		assert_eq!(
+			error.into_results(),
+			[Ok(10), Ok(20), Err(WalletError::UserRejected)]
+		);
+	}
+
+	#[test]
+	fn output_count_must_match() {
+		assert_eq!(expect_outputs(2, vec![1, 2]), Ok(vec![1, 2]));
+		assert_eq!(
+			expect_outputs(2, vec![1]),
+			Err(WalletError::UnexpectedOutputCount {
+				expected: 2,
+				received: 1
+			})
+		);
+	}
+}
@@ -0,0 +1,406 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ChainId;
+use crate::WalletError;
+use crate::WalletResult;
+
+/// The CAIP-2 namespace used by Solana.
+pub const SOLANA_CAIP2_NAMESPACE: &str = "solana";
+
+pub const SOLANA_MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+pub const SOLANA_DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+pub const SOLANA_TESTNET_GENESIS_HASH: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+
+/// The wallet standard chains with a canonical genesis hash.
+const SOLANA_GENESIS_HASHES: [(&str, &str); 3] = [
+	("mainnet", SOLANA_MAINNET_GENESIS_HASH),
+	("devnet", SOLANA_DEVNET_GENESIS_HASH),
+	("testnet", SOLANA_TESTNET_GENESIS_HASH),
+];
+
+/// The maximum length of the reference of a CAIP-2 chain id.
+const CAIP2_REFERENCE_MAX_LENGTH: usize = 32;
+
+/// A [CAIP-2](https://github.com/ChainAgnostic/CAIPs/blob/main/CAIPs/caip-2.md)
+/// chain id in the form `namespace:reference`.
+///
+/// The namespace must match `[-a-z0-9]{3,8}` and the reference must match
+/// `[-_a-zA-Z0-9]{1,32}`.
+///
+/// Solana chains are identified by the first 32 characters of their genesis
+/// hash. The wallet standard short forms `solana:mainnet`, `solana:devnet` and
+/// `solana:testnet` can be converted to and from this form.
+///
+/// ```
+/// use wallet_standard::Caip2ChainId;
+/// use wallet_standard::ChainId;
+///
+/// let caip2 = Caip2ChainId::from_wallet_chain("solana:mainnet")?;
+///
+/// assert_eq!(caip2.to_string(), "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp");
+/// assert_eq!(caip2.to_chain_id()?, ChainId::parse("solana:mainnet")?);
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Caip2ChainId {
+	namespace: String,
+	reference: String,
+}
+
+impl Caip2ChainId {
+	/// Create a chain id from its parts.
+	pub fn new(namespace: impl Into<String>, reference: impl Into<String>) -> WalletResult<Self> {
+		let namespace = namespace.into();
+		let reference = reference.into();
+
+		if !is_caip2_namespace(&namespace) {
+			return Err(invalid_caip(
+				&format!("{namespace}:{reference}"),
+				"the namespace must match `[-a-z0-9]{3,8}`",
+			));
+		}
+
+		if !is_caip2_reference(&reference) {
+			return Err(invalid_caip(
+				&format!("{namespace}:{reference}"),
+				"the reference must match `[-_a-zA-Z0-9]{1,32}`",
+			));
+		}
+
+		Ok(Self {
+			namespace,
+			reference,
+		})
+	}
+
+	/// Parse a CAIP-2 chain id.
+	pub fn parse(value: &str) -> WalletResult<Self> {
+		let Some((namespace, reference)) = value.split_once(':') else {
+			return Err(invalid_caip(
+				value,
+				"expected a chain id in the form `namespace:reference`",
+			));
+		};
+
+		Self::new(namespace, reference)
+	}
+
+	/// Create the chain id of a Solana cluster from its base58 encoded genesis
+	/// hash. This is useful for clusters like `solana:localnet` which don't
+	/// have a canonical genesis hash.
+	pub fn solana(genesis_hash: &str) -> WalletResult<Self> {
+		Self::new(SOLANA_CAIP2_NAMESPACE, solana_reference(genesis_hash))
+	}
+
+	/// Convert a wallet standard chain to a CAIP-2 chain id.
+	///
+	/// The Solana short forms are converted to their genesis hash form. Chains
+	/// which are already valid CAIP-2 chain ids are returned unchanged.
+	/// `solana:localnet` has no canonical genesis hash and fails, use
+	/// [`Caip2ChainId::solana`] instead.
+	pub fn from_wallet_chain(chain: &str) -> WalletResult<Self> {
+		let chain = ChainId::parse(chain)?;
+
+		if chain.namespace() == SOLANA_CAIP2_NAMESPACE {
+			if let Some((_, genesis_hash)) = SOLANA_GENESIS_HASHES
+				.iter()
+				.find(|(name, _)| *name == chain.reference())
+			{
+				return Self::solana(genesis_hash);
+			}
+
+			if chain.reference() == "localnet" {
+				return Err(invalid_caip(
+					chain.as_str(),
+					"localnet has no canonical genesis hash",
+				));
+			}
+		}
+
+		Self::parse(chain.as_str())
+	}
+
+	/// Convert the CAIP-2 chain id to a wallet standard chain.
+	///
+	/// Solana chain ids with a known genesis hash are converted to their short
+	/// form. Every other chain id is returned unchanged.
+	pub fn to_chain_id(&self) -> WalletResult<ChainId> {
+		if let Some(name) = self.solana_cluster() {
+			return ChainId::parse(format!("{SOLANA_CAIP2_NAMESPACE}:{name}"));
+		}
+
+		ChainId::parse(self.to_string())
+	}
+
+	/// The wallet standard name of the Solana cluster, e.g. `mainnet`, when
+	/// this is the chain id of a well known Solana cluster.
+	pub fn solana_cluster(&self) -> Option<&'static str> {
+		if self.namespace != SOLANA_CAIP2_NAMESPACE {
+			return None;
+		}
+
+		SOLANA_GENESIS_HASHES
+			.iter()
+			.find(|(_, genesis_hash)| solana_reference(genesis_hash) == self.reference)
+			.map(|(name, _)| *name)
+	}
+
+	pub fn namespace(&self) -> &str {
+		&self.namespace
+	}
+
+	pub fn reference(&self) -> &str {
+		&self.reference
+	}
+}
+
+/// A [CAIP-10](https://github.com/ChainAgnostic/CAIPs/blob/main/CAIPs/caip-10.md)
+/// account id in the form `namespace:reference:address`.
+///
+/// ```
+/// use wallet_standard::Caip10AccountId;
+///
+/// let account: Caip10AccountId = "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp:\
+///                                 HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH"
+/// 	.parse()?;
+///
+/// assert_eq!(account.chain_id().solana_cluster(), Some("mainnet"));
+/// assert_eq!(
+/// 	account.address(),
+/// 	"HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH"
+/// );
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Caip10AccountId {
+	chain_id: Caip2ChainId,
+	address: String,
+}
+
+impl Caip10AccountId {
+	/// Create an account id from a chain id and an address. The address must
+	/// match `[-.%a-zA-Z0-9]{1,128}`.
+	pub fn new(chain_id: Caip2ChainId, address: impl Into<String>) -> WalletResult<Self> {
+		let address = address.into();
+
+		if !is_caip10_address(&address) {
+			return Err(invalid_caip(
+				&format!("{chain_id}:{address}"),
+				"the address must match `[-.%a-zA-Z0-9]{1,128}`",
+			));
+		}
+
+		Ok(Self { chain_id, address })
+	}
+
+	/// Parse a CAIP-10 account id.
+	pub fn parse(value: &str) -> WalletResult<Self> {
+		let Some((chain_id, address)) = value.rsplit_once(':') else {
+			return Err(invalid_caip(
+				value,
+				"expected an account id in the form `namespace:reference:address`",
+			));
+		};
+
+		Self::new(Caip2ChainId::parse(chain_id)?, address)
+	}
+
+	/// Create an account id from a wallet standard chain, such as
+	/// `solana:devnet`, and an address.
+	pub fn from_wallet_chain(chain: &str, address: impl Into<String>) -> WalletResult<Self> {
+		Self::new(Caip2ChainId::from_wallet_chain(chain)?, address)
+	}
+
+	pub fn chain_id(&self) -> &Caip2ChainId {
+		&self.chain_id
+	}
+
+	pub fn address(&self) -> &str {
+		&self.address
+	}
+}
+
+fn is_caip2_namespace(value: &str) -> bool {
+	(3..=8).contains(&value.len())
+		&& value
+			.bytes()
+			.all(|byte| byte == b'-' || byte.is_ascii_lowercase() || byte.is_ascii_digit())
+}
+
+fn is_caip2_reference(value: &str) -> bool {
+	(1..=CAIP2_REFERENCE_MAX_LENGTH).contains(&value.len())
+		&& value
+			.bytes()
+			.all(|byte| byte == b'-' || byte == b'_' || byte.is_ascii_alphanumeric())
+}
+
+fn is_caip10_address(value: &str) -> bool {
+	(1..=128).contains(&value.len())
+		&& value
+			.bytes()
+			.all(|byte| matches!(byte, b'-' | b'.' | b'%') || byte.is_ascii_alphanumeric())
+}
+
+/// Solana CAIP-2 references are the first 32 characters of the base58
+/// encoded genesis hash.
+fn solana_reference(genesis_hash: &str) -> &str {
+	genesis_hash
+		.get(..CAIP2_REFERENCE_MAX_LENGTH)
+		.unwrap_or(genesis_hash)
+}
+
+fn invalid_caip(value: &str, reason: &str) -> WalletError {
+	WalletError::InvalidIdentifier(format!("`{value}`: {reason}"))
+}
+
+impl fmt::Display for Caip2ChainId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}", self.namespace, self.reference)
+	}
+}
+
+impl fmt::Display for Caip10AccountId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}", self.chain_id, self.address)
+	}
+}
+
+macro_rules! caip_conversions {
+	($name:ident) => {
+		impl FromStr for $name {
+			type Err = WalletError;
+
+			fn from_str(value: &str) -> WalletResult<Self> {
+				Self::parse(value)
+			}
+		}
+
+		impl TryFrom<String> for $name {
+			type Error = WalletError;
+
+			fn try_from(value: String) -> WalletResult<Self> {
+				Self::parse(&value)
+			}
+		}
+
+		impl TryFrom<&str> for $name {
+			type Error = WalletError;
+
+			fn try_from(value: &str) -> WalletResult<Self> {
+				Self::parse(value)
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(value: $name) -> Self {
+				value.to_string()
+			}
+		}
+	};
+}
+
+caip_conversions!(Caip2ChainId);
+caip_conversions!(Caip10AccountId);
+
+impl TryFrom<&ChainId> for Caip2ChainId {
+	type Error = WalletError;
+
+	fn try_from(chain: &ChainId) -> WalletResult<Self> {
+		Self::from_wallet_chain(chain.as_str())
+	}
+}
+
+impl TryFrom<&Caip2ChainId> for ChainId {
+	type Error = WalletError;
+
+	fn try_from(chain: &Caip2ChainId) -> WalletResult<Self> {
+		chain.to_chain_id()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ADDRESS: &str = "HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH";
+
+	#[test]
+	fn solana_short_forms_round_trip() -> WalletResult<()> {
+		for (chain, caip2) in [
+			("solana:mainnet", "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp"),
+			("solana:devnet", "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1"),
+			("solana:testnet", "solana:4uhcVJyU9pJkvQyS88uRDiswHXSCkY3z"),
+		] {
+			let chain_id = Caip2ChainId::from_wallet_chain(chain)?;
+
+			assert_eq!(chain_id.to_string(), caip2);
+			assert_eq!(Caip2ChainId::parse(caip2)?.to_chain_id()?, chain);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn localnet_requires_a_genesis_hash() -> WalletResult<()> {
+		assert!(Caip2ChainId::from_wallet_chain("solana:localnet").is_err());
+
+		let localnet = Caip2ChainId::solana("9oGcn1Vx5KNmWaDyrvrFFyfUxSFkx1MyWvqcKLW7h1Hm")?;
+
+		assert_eq!(localnet.reference(), "9oGcn1Vx5KNmWaDyrvrFFyfUxSFkx1My");
+		assert_eq!(localnet.solana_cluster(), None);
+		assert_eq!(localnet.to_chain_id()?, localnet.to_string());
+
+		Ok(())
+	}
+
+	#[test]
+	fn other_chains_are_unchanged() -> WalletResult<()> {
+		let chain_id = Caip2ChainId::from_wallet_chain("eip155:1")?;
+
+		assert_eq!(chain_id.namespace(), "eip155");
+		assert_eq!(chain_id.reference(), "1");
+		assert_eq!(chain_id.to_chain_id()?, "eip155:1");
+
+		Ok(())
+	}
+
+	#[test]
+	fn invalid_caip2_chain_ids() {
+		for invalid in [
+			"solana",
+			"so:mainnet",
+			"Solana:mainnet",
+			"solana:",
+			"solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d",
+			"solana:main.net",
+		] {
+			assert!(
+				matches!(
+					Caip2ChainId::parse(invalid),
+					Err(WalletError::InvalidIdentifier(_))
+				),
+				"{invalid}"
+			);
+		}
+	}
+
+	#[test]
+	fn caip10_account_ids() -> WalletResult<()> {
+		let account = Caip10AccountId::from_wallet_chain("solana:devnet", ADDRESS)?;
+		let formatted = format!("solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1:{ADDRESS}");
+
+		assert_eq!(account.to_string(), formatted);
+		assert_eq!(Caip10AccountId::parse(&formatted)?, account);
+		assert_eq!(account.chain_id().solana_cluster(), Some("devnet"));
+		assert!(Caip10AccountId::parse("solana:mainnet").is_err());
+		assert!(Caip10AccountId::parse("solana:mainnet:not/valid").is_err());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,458 @@
+//! A conformance suite for [`WalletSolana`] implementations.
+//!
+//! The wallet standard places invariants on wallets which the type system
+//! can't enforce. For example, the chains of an account must be a subset of
+//! the chains of the wallet, and connecting must populate the wallet account.
+//! [`run_all`] checks these rules against any implementation and returns a
+//! [`ConformanceReport`] which can be asserted on.
+//!
+//! ```rust
+//! use solana_keypair::Keypair;
+//! use wallet_standard::KeypairWallet;
+//! use wallet_standard::conformance;
+//!
+//! # futures::executor::block_on(async {
+//! let mut wallet = KeypairWallet::new(Keypair::new());
+//! let report = conformance::run_all(&mut wallet).await;
+//!
+//! assert!(report.is_conformant(), "{:#?}", report.failures());
+//! # });
+//! ```
+
+use serde::Deserialize;
+use serde::Serialize;
+use solana_pubkey::Pubkey;
+use typed_builder::TypedBuilder;
+
+use crate::ChainId;
+use crate::SolanaSignInInput;
+use crate::SolanaSignMessageOutput;
+use crate::SolanaSignatureOutput;
+use crate::Wallet;
+use crate::WalletAccountInfo;
+use crate::WalletError;
+use crate::WalletInfo;
+use crate::WalletResult;
+use crate::WalletSolana;
+use crate::WalletSolanaSignIn;
+use crate::WalletSolanaSignMessage;
+use crate::WalletStandardDisconnect;
+use crate::verify_sign_in;
+
+/// A rule checked by the conformance suite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConformanceRule {
+	/// The chains of the wallet must not use the `standard` or
+	/// `experimental` namespaces.
+	WalletChainNamespaces,
+	/// The chains of every account must be a subset of the wallet chains.
+	AccountChainsSubset,
+	/// The features of every account must be a subset of the wallet features.
+	AccountFeaturesSubset,
+	/// Connecting must populate the wallet account with one of the returned
+	/// accounts.
+	ConnectPopulatesAccount,
+	/// The message returned by `solana:signMessage` must be the requested
+	/// message, and the signature must verify against it and the public key of
+	/// the account.
+	SignMessageVerifies,
+	/// The output of `solana:signIn` must pass [`verify_sign_in`].
+	SignInVerifies,
+	/// Disconnecting must clear the wallet account.
+	DisconnectClearsAccount,
+}
+
+/// The outcome of checking a single [`ConformanceRule`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status", content = "reason")]
+pub enum ConformanceOutcome {
+	Passed,
+	Failed(String),
+	/// The rule couldn't be checked, usually because an earlier step failed.
+	Skipped(String),
+}
+
+/// A checked rule and its outcome.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConformanceCheck {
+	pub rule: ConformanceRule,
+	pub outcome: ConformanceOutcome,
+}
+
+impl ConformanceCheck {
+	pub fn passed(&self) -> bool {
+		self.outcome == ConformanceOutcome::Passed
+	}
+
+	pub fn failed(&self) -> bool {
+		matches!(self.outcome, ConformanceOutcome::Failed(_))
+	}
+}
+
+/// The result of running the conformance suite.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConformanceReport {
+	/// Every check which was run, in order.
+	pub checks: Vec<ConformanceCheck>,
+}
+
+impl ConformanceReport {
+	/// Whether no rule failed. Skipped rules don't count as failures.
+	pub fn is_conformant(&self) -> bool {
+		!self.checks.iter().any(ConformanceCheck::failed)
+	}
+
+	/// The checks which failed.
+	pub fn failures(&self) -> Vec<&ConformanceCheck> {
+		self.checks.iter().filter(|check| check.failed()).collect()
+	}
+
+	/// The outcome of the provided rule. When a rule is checked more than once
+	/// the first failure is returned, otherwise the last outcome.
+	pub fn outcome(&self, rule: ConformanceRule) -> Option<&ConformanceOutcome> {
+		let mut checks = self.checks.iter().filter(|check| check.rule == rule);
+
+		checks
+			.clone()
+			.find(|check| check.failed())
+			.or_else(|| checks.next_back())
+			.map(|check| &check.outcome)
+	}
+
+	fn push(&mut self, rule: ConformanceRule, outcome: ConformanceOutcome) {
+		self.checks.push(ConformanceCheck { rule, outcome });
+	}
+
+	fn push_result(&mut self, rule: ConformanceRule, result: Result<(), String>) {
+		let outcome = match result {
+			Ok(()) => ConformanceOutcome::Passed,
+			Err(reason) => ConformanceOutcome::Failed(reason),
+		};
+
+		self.push(rule, outcome);
+	}
+
+	fn skip(&mut self, rule: ConformanceRule, reason: impl Into<String>) {
+		self.push(rule, ConformanceOutcome::Skipped(reason.into()));
+	}
+}
+
+/// Options for the conformance suite.
+#[derive(Clone, Debug, PartialEq, Eq, TypedBuilder)]
+pub struct ConformanceOptions {
+	/// The message signed when checking `solana:signMessage`.
+	#[builder(default = b"wallet standard conformance".to_vec(), setter(into))]
+	pub message: Vec<u8>,
+	/// The input used when checking `solana:signIn`. The address is filled in
+	/// with the connected account.
+	#[builder(default = default_sign_in_input())]
+	pub sign_in_input: SolanaSignInInput,
+}
+
+impl Default for ConformanceOptions {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+fn default_sign_in_input() -> SolanaSignInInput {
+	SolanaSignInInput::builder()
+		.domain("localhost")
+		.statement("Wallet standard conformance")
+		.nonce("conformance")
+		.build()
+}
+
+/// Run every conformance check against the wallet with the default options.
+///
+/// The wallet is connected, used to sign a message and sign in, and then
+/// disconnected.
+pub async fn run_all<W: WalletSolana>(wallet: &mut W) -> ConformanceReport {
+	run_all_with_options(wallet, &ConformanceOptions::default()).await
+}
+
+/// Run every conformance check against the wallet.
+pub async fn run_all_with_options<W: WalletSolana>(
+	wallet: &mut W,
+	options: &ConformanceOptions,
+) -> ConformanceReport {
+	let mut report = ConformanceReport::default();
+
+	check_wallet_info(&mut report, wallet);
+
+	match wallet.connect().await {
+		Ok(accounts) => {
+			report.push_result(
+				ConformanceRule::ConnectPopulatesAccount,
+				check_connect_populates_account(wallet, &accounts),
+			);
+			check_wallet_info(&mut report, wallet);
+		}
+		Err(error) => {
+			report.push(
+				ConformanceRule::ConnectPopulatesAccount,
+				ConformanceOutcome::Failed(format!("connect failed: {error}")),
+			);
+		}
+	}
+
+	if let Some(account) = wallet.wallet_account() {
+		report.push_result(
+			ConformanceRule::SignMessageVerifies,
+			check_sign_message(wallet, &account, &options.message).await,
+		);
+
+		let input = SolanaSignInInput {
+			address: Some(account.address()),
+			..options.sign_in_input.clone()
+		};
+		report.push_result(
+			ConformanceRule::SignInVerifies,
+			check_sign_in(wallet, &input).await,
+		);
+
+		report.push_result(
+			ConformanceRule::DisconnectClearsAccount,
+			check_disconnect(wallet).await,
+		);
+	} else {
+		let reason = "the wallet has no connected account";
+		report.skip(ConformanceRule::SignMessageVerifies, reason);
+		report.skip(ConformanceRule::SignInVerifies, reason);
+		report.skip(ConformanceRule::DisconnectClearsAccount, reason);
+	}
+
+	report
+}
+
+/// Check the chain namespaces of the wallet and that the chains and features
+/// of every account are a subset of those of the wallet.
+fn check_wallet_info<W: Wallet>(report: &mut ConformanceReport, wallet: &W) {
+	let info = wallet.wallet();
+	let chains = info.chains();
+	let features = info.features();
+
+	report.push_result(
+		ConformanceRule::WalletChainNamespaces,
+		check_chain_namespaces(&chains),
+	);
+
+	let mut chain_errors = vec![];
+	let mut feature_errors = vec![];
+	let accounts = info
+		.accounts()
+		.into_iter()
+		.map(|account| account_summary(&account))
+		.chain(
+			wallet
+				.wallet_account()
+				.map(|account| account_summary(&account)),
+		);
+
+	for (address, account_chains, account_features) in accounts {
+		chain_errors.extend(missing(&account_chains, &chains).map(|chain| {
+			format!("account `{address}` supports chain `{chain}` which the wallet doesn't")
+		}));
+		feature_errors.extend(missing(&account_features, &features).map(|feature| {
+			format!("account `{address}` supports feature `{feature}` which the wallet doesn't")
+		}));
+	}
+
+	report.push_result(ConformanceRule::AccountChainsSubset, joined(&chain_errors));
+	report.push_result(
+		ConformanceRule::AccountFeaturesSubset,
+		joined(&feature_errors),
+	);
+}
+
+fn account_summary(account: &impl WalletAccountInfo) -> (String, Vec<String>, Vec<String>) {
+	(account.address(), account.chains(), account.features())
+}
+
+fn missing<'a>(values: &'a [String], allowed: &'a [String]) -> impl Iterator<Item = &'a String> {
+	values.iter().filter(|value| !allowed.contains(value))
+}
+
+fn joined(errors: &[String]) -> Result<(), String> {
+	if errors.is_empty() {
+		Ok(())
+	} else {
+		Err(errors.join("; "))
+	}
+}
+
+fn check_chain_namespaces(chains: &[String]) -> Result<(), String> {
+	let errors = chains
+		.iter()
+		.filter_map(|chain| ChainId::parse(chain.as_str()).err())
+		.map(|error| error.to_string())
+		.collect::<Vec<_>>();
+
+	joined(&errors)
+}
+
+fn check_connect_populates_account<W: Wallet>(
+	wallet: &W,
+	accounts: &[W::Account],
+) -> Result<(), String> {
+	let Some(account) = wallet.wallet_account() else {
+		return Err("connect didn't populate the wallet account".into());
+	};
+
+	if !wallet.connected() {
+		return Err("the wallet isn't connected after connecting".into());
+	}
+
+	let address = account.address();
+
+	if accounts.iter().any(|account| account.address() == address) {
+		Ok(())
+	} else {
+		Err(format!(
+			"the wallet account `{address}` isn't one of the accounts returned by connect"
+		))
+	}
+}
+
+async fn check_sign_message<W: WalletSolanaSignMessage>(
+	wallet: &W,
+	account: &impl WalletAccountInfo,
+	message: &[u8],
+) -> Result<(), String> {
+	let output = wallet
+		.sign_message_async(message)
+		.await
+		.map_err(|error| format!("sign_message failed: {error}"))?;
+	let pubkey = account_pubkey(account).map_err(|error| error.to_string())?;
+	let signature = output
+		.try_signature()
+		.map_err(|error| format!("invalid signature: {error}"))?;
+	let signed_message = output.signed_message();
+
+	if signed_message != message {
+		return Err(format!(
+			"the signed message {signed_message:?} isn't the requested message {message:?}"
+		));
+	}
+
+	if signature.verify(pubkey.as_ref(), &signed_message) {
+		Ok(())
+	} else {
+		Err("the signature doesn't verify against the signed message".into())
+	}
+}
+
+async fn check_sign_in<W: WalletSolanaSignIn>(
+	wallet: &W,
+	input: &SolanaSignInInput,
+) -> Result<(), String> {
+	let output = wallet
+		.sign_in(input.clone())
+		.await
+		.map_err(|error| format!("sign_in failed: {error}"))?;
+
+	verify_sign_in(input, &output).map_err(|error| format!("verify_sign_in failed: {error}"))
+}
+
+async fn check_disconnect<W: WalletStandardDisconnect>(wallet: &mut W) -> Result<(), String> {
+	wallet
+		.disconnect()
+		.await
+		.map_err(|error| format!("disconnect failed: {error}"))?;
+
+	if wallet.wallet_account().is_some() || wallet.connected() {
+		Err("the wallet account is still set after disconnecting".into())
+	} else {
+		Ok(())
+	}
+}
+
+fn account_pubkey(account: &impl WalletAccountInfo) -> WalletResult<Pubkey> {
+	Pubkey::try_from(account.public_key()).map_err(|_| WalletError::WalletPublicKey)
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+	use solana_keypair::Keypair;
+
+	use super::*;
+	use crate::KeypairWallet;
+
+	#[test]
+	fn keypair_wallet_is_conformant() {
+		let mut wallet = KeypairWallet::builder()
+			.keypairs(vec![Keypair::new(), Keypair::new()])
+			.build();
+		let report = block_on(run_all(&mut wallet));
+
+		assert!(report.is_conformant(), "{:#?}", report.failures());
+		assert!(report.checks.iter().all(ConformanceCheck::passed));
+	}
+
+	#[test]
+	fn reserved_chain_namespaces_fail() {
+		let mut wallet = KeypairWallet::builder()
+			.keypairs(vec![Keypair::new()])
+			.chains(vec!["standard:mainnet".to_string()])
+			.build();
+		let report = block_on(run_all(&mut wallet));
+
+		assert!(matches!(
+			report.outcome(ConformanceRule::WalletChainNamespaces),
+			Some(ConformanceOutcome::Failed(_))
+		));
+	}
+
+	#[cfg(feature = "testing")]
+	#[test]
+	fn failures_are_reported() {
+		use solana_signature::Signature;
+		use solana_signer::Signer;
+
+		use crate::testing::MockResponse;
+		use crate::testing::MockWallet;
+
+		let mut wallet = MockWallet::default();
+		wallet.handle().push_sign_message(MockResponse::Ok((
+			Signature::default(),
+			b"forged".to_vec(),
+			None,
+		)));
+		let report = block_on(run_all(&mut wallet));
+
+		assert!(!report.is_conformant());
+		assert_eq!(report.failures().len(), 1);
+		assert!(matches!(
+			report.outcome(ConformanceRule::SignMessageVerifies),
+			Some(ConformanceOutcome::Failed(_))
+		));
+
+		let keypair = Keypair::new();
+		let message = b"prefix: wallet standard conformance".to_vec();
+		let signature = keypair.sign_message(&message);
+		let mut wallet = MockWallet::new(KeypairWallet::new(keypair));
+		wallet
+			.handle()
+			.push_sign_message(MockResponse::Ok((signature, message, None)));
+		let report = block_on(run_all(&mut wallet));
+
+		assert_eq!(report.failures().len(), 1);
+		assert!(matches!(
+			report.outcome(ConformanceRule::SignMessageVerifies),
+			Some(ConformanceOutcome::Failed(reason)) if reason.contains("requested message")
+		));
+
+		let mut wallet = MockWallet::default();
+		wallet
+			.handle()
+			.push_connect(MockResponse::Err(WalletError::WalletWindowClosed));
+		let report = block_on(run_all(&mut wallet));
+
+		assert!(matches!(
+			report.outcome(ConformanceRule::SignInVerifies),
+			Some(ConformanceOutcome::Skipped(_))
+		));
+	}
+}
@@ -0,0 +1,702 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::rc::Weak;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::StandardConnectInput;
+use crate::Wallet;
+use crate::WalletAccounts;
+use crate::WalletError;
+use crate::WalletResult;
+use crate::WalletStandard;
+use crate::WalletStandardConnect;
+use crate::WalletStandardDisconnect;
+
+/// The lifecycle of the connection between an app and a wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", content = "error", rename_all = "camelCase")]
+pub enum ConnectionState {
+	/// The wallet has not been registered.
+	NotDetected,
+	/// The wallet is not installed, but can be loaded, e.g. through a deeplink.
+	Loadable,
+	/// The wallet has been detected and is not connected.
+	Ready,
+	/// A connection is in progress.
+	Connecting,
+	/// The app is authorized to use at least one account.
+	Connected,
+	/// A disconnection is in progress.
+	Disconnecting,
+	/// The last connection or disconnection failed.
+	Errored(WalletError),
+}
+
+impl ConnectionState {
+	/// The name of the state, e.g. `connecting`.
+	pub const fn as_str(&self) -> &'static str {
+		match self {
+			Self::NotDetected => "notDetected",
+			Self::Loadable => "loadable",
+			Self::Ready => "ready",
+			Self::Connecting => "connecting",
+			Self::Connected => "connected",
+			Self::Disconnecting => "disconnecting",
+			Self::Errored(_) => "errored",
+		}
+	}
+
+	/// Whether a connection or disconnection is in progress.
+	pub const fn is_pending(&self) -> bool {
+		matches!(self, Self::Connecting | Self::Disconnecting)
+	}
+
+	pub const fn is_connected(&self) -> bool {
+		matches!(self, Self::Connected)
+	}
+
+	/// Whether the state machine allows moving from `self` to `next`.
+	///
+	/// Moving to the current state is never allowed, which is how concurrent
+	/// connections and disconnections are rejected.
+	pub const fn can_transition_to(&self, next: &Self) -> bool {
+		match self {
+			Self::NotDetected => matches!(next, Self::Loadable | Self::Ready | Self::Errored(_)),
+			Self::Loadable => matches!(next, Self::NotDetected | Self::Ready | Self::Errored(_)),
+			Self::Ready => {
+				matches!(
+					next,
+					Self::NotDetected | Self::Loadable | Self::Connecting | Self::Errored(_)
+				)
+			}
+			Self::Connecting => matches!(next, Self::Connected | Self::Ready | Self::Errored(_)),
+			Self::Connected => {
+				matches!(
+					next,
+					Self::NotDetected
+						| Self::Ready | Self::Connecting
+						| Self::Disconnecting
+						| Self::Errored(_)
+				)
+			}
+			Self::Disconnecting => matches!(next, Self::Ready | Self::Connected | Self::Errored(_)),
+			Self::Errored(_) => {
+				matches!(
+					next,
+					Self::NotDetected
+						| Self::Loadable | Self::Ready
+						| Self::Connecting | Self::Disconnecting
+				)
+			}
+		}
+	}
+}
+
+impl fmt::Display for ConnectionState {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// A change of [`ConnectionState`] which is sent to every subscriber.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionStateChange {
+	pub previous: ConnectionState,
+	pub current: ConnectionState,
+}
+
+type ConnectionListener = Rc<dyn Fn(&ConnectionStateChange)>;
+
+struct ConnectionStateInner {
+	state: ConnectionState,
+	next_id: u64,
+	listeners: Vec<(u64, ConnectionListener)>,
+}
+
+/// A shared handle to a [`ConnectionState`] which enforces legal transitions
+/// and notifies subscribers on every change.
+///
+/// The handle can be kept by the UI after the wallet has been moved into a
+/// [`StatefulWallet`].
+#[derive(Clone)]
+pub struct ConnectionStateHandle(Rc<RefCell<ConnectionStateInner>>);
+
+impl ConnectionStateHandle {
+	pub fn new(state: ConnectionState) -> Self {
+		Self(Rc::new(RefCell::new(ConnectionStateInner {
+			state,
+			next_id: 0,
+			listeners: Vec::new(),
+		})))
+	}
+
+	/// The current state.
+	pub fn state(&self) -> ConnectionState {
+		self.0.borrow().state.clone()
+	}
+
+	/// Move to the `next` state and notify every subscriber.
+	///
+	/// # Errors
+	///
+	/// Returns [`WalletError::InvalidConnectionTransition`] when the state
+	/// machine doesn't allow the transition.
+	pub fn transition(&self, next: ConnectionState) -> WalletResult<ConnectionStateChange> {
+		let (change, listeners) = {
+			let mut inner = self.0.borrow_mut();
+
+			if !inner.state.can_transition_to(&next) {
+				return Err(WalletError::InvalidConnectionTransition {
+					from: inner.state.to_string(),
+					to: next.to_string(),
+				});
+			}
+
+			let previous = std::mem::replace(&mut inner.state, next.clone());
+			let listeners = inner
+				.listeners
+				.iter()
+				.map(|(_, listener)| listener.clone())
+				.collect::<Vec<_>>();
+
+			(
+				ConnectionStateChange {
+					previous,
+					current: next,
+				},
+				listeners,
+			)
+		};
+
+		// Listeners are called without holding the borrow, so they can
+		// subscribe, unsubscribe or read the state.
+		for listener in listeners {
+			listener(&change);
+		}
+
+		Ok(change)
+	}
+
+	/// Call the `listener` on every state change until the returned
+	/// subscription is dropped.
+	#[must_use = "the listener is removed when the subscription is dropped"]
+	pub fn subscribe(
+		&self,
+		listener: impl Fn(&ConnectionStateChange) + 'static,
+	) -> ConnectionSubscription {
+		let mut inner = self.0.borrow_mut();
+		let id = inner.next_id;
+		inner.next_id += 1;
+		inner.listeners.push((id, Rc::new(listener)));
+
+		ConnectionSubscription {
+			inner: Rc::downgrade(&self.0),
+			id,
+		}
+	}
+}
+
+impl fmt::Debug for ConnectionStateHandle {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let inner = self.0.borrow();
+
+		f.debug_struct("ConnectionStateHandle")
+			.field("state", &inner.state)
+			.field("listeners", &inner.listeners.len())
+			.finish()
+	}
+}
+
+/// Removes its listener from the [`ConnectionStateHandle`] when dropped.
+#[derive(Debug)]
+pub struct ConnectionSubscription {
+	inner: Weak<RefCell<ConnectionStateInner>>,
+	id: u64,
+}
+
+impl Drop for ConnectionSubscription {
+	fn drop(&mut self) {
+		if let Some(inner) = self.inner.upgrade() {
+			inner
+				.borrow_mut()
+				.listeners
+				.retain(|(id, _)| *id != self.id);
+		}
+	}
+}
+
+/// Restores the previous state when a connection or disconnection future is
+/// dropped before it completes.
+struct PendingTransition<'a> {
+	handle: &'a ConnectionStateHandle,
+	revert: Option<ConnectionState>,
+}
+
+impl<'a> PendingTransition<'a> {
+	fn start(handle: &'a ConnectionStateHandle, next: ConnectionState) -> WalletResult<Self> {
+		let change = handle.transition(next)?;
+
+		Ok(Self {
+			handle,
+			revert: Some(change.previous),
+		})
+	}
+
+	/// Move to the `next` state. A rejected transition, e.g. when the state
+	/// was changed through the handle in the meantime, is logged so it doesn't
+	/// hide the result of the wallet call.
+	fn finish(mut self, next: ConnectionState) {
+		self.revert = None;
+
+		if let Err(error) = self.handle.transition(next) {
+			log::warn!("the connection state wasn't updated: {error}");
+		}
+	}
+}
+
+impl Drop for PendingTransition<'_> {
+	fn drop(&mut self) {
+		if let Some(previous) = self.revert.take() {
+			self.handle.transition(previous).ok();
+		}
+	}
+}
+
+/// A wrapper around any [`WalletStandard`] which tracks its
+/// [`ConnectionState`].
+///
+/// Connecting while a connection or disconnection is in progress fails with
+/// [`WalletError::InvalidConnectionTransition`]. The wrapper implements the
+/// same traits as the wallet it wraps, so it can be used in its place.
+///
+/// ```rust
+/// # #[cfg(feature = "solana")]
+/// # {
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use solana_keypair::Keypair;
+/// use wallet_standard::ConnectionState;
+/// use wallet_standard::KeypairWallet;
+/// use wallet_standard::StatefulWallet;
+/// use wallet_standard::prelude::*;
+///
+/// # futures::executor::block_on(async {
+/// let mut wallet = StatefulWallet::new(KeypairWallet::new(Keypair::new()));
+/// let states = Rc::new(RefCell::new(vec![]));
+/// let _subscription = wallet.handle().subscribe({
+/// 	let states = states.clone();
+/// 	move |change| states.borrow_mut().push(change.current.clone())
+/// });
+///
+/// wallet.connect().await?;
+/// assert_eq!(
+/// 	*states.borrow(),
+/// 	[ConnectionState::Connecting, ConnectionState::Connected]
+/// );
+/// # WalletResult::Ok(())
+/// # })
+/// # .unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StatefulWallet<W> {
+	wallet: W,
+	handle: ConnectionStateHandle,
+}
+
+impl<W: Wallet> StatefulWallet<W> {
+	/// Wrap a detected wallet. The initial state is [`ConnectionState::Ready`],
+	/// or [`ConnectionState::Connected`] when the wallet is already connected.
+	pub fn new(wallet: W) -> Self {
+		let state = if wallet.connected() {
+			ConnectionState::Connected
+		} else {
+			ConnectionState::Ready
+		};
+
+		Self::with_state(wallet, state)
+	}
+
+	/// Wrap the wallet with the provided initial state.
+	pub fn with_state(wallet: W, state: ConnectionState) -> Self {
+		Self {
+			wallet,
+			handle: ConnectionStateHandle::new(state),
+		}
+	}
+
+	/// The current state.
+	pub fn state(&self) -> ConnectionState {
+		self.handle.state()
+	}
+
+	/// A shared handle for observing the state.
+	pub fn handle(&self) -> ConnectionStateHandle {
+		self.handle.clone()
+	}
+
+	/// The wrapped wallet.
+	pub fn inner(&self) -> &W {
+		&self.wallet
+	}
+
+	pub fn into_inner(self) -> W {
+		self.wallet
+	}
+
+	/// The state after a connection or disconnection failed. The wallet is
+	/// still [`ConnectionState::Connected`] when it kept its account, e.g.
+	/// after a failed reconnection.
+	fn failed_state(&self, error: &WalletError) -> ConnectionState {
+		if self.wallet.connected() {
+			ConnectionState::Connected
+		} else {
+			ConnectionState::Errored(error.clone())
+		}
+	}
+
+	/// Move to [`ConnectionState::Ready`] when the wallet has lost every
+	/// account outside of [`WalletStandardDisconnect::disconnect`], e.g. after
+	/// a `change` event.
+	pub fn sync_state(&self) -> WalletResult<ConnectionState> {
+		if self.handle.state().is_connected() && !self.wallet.connected() {
+			self.handle.transition(ConnectionState::Ready)?;
+		}
+
+		Ok(self.handle.state())
+	}
+}
+
+impl<W: Wallet> Wallet for StatefulWallet<W> {
+	type Account = W::Account;
+	type Wallet = W::Wallet;
+
+	fn wallet(&self) -> Self::Wallet {
+		self.wallet.wallet()
+	}
+
+	fn wallet_account(&self) -> Option<Self::Account> {
+		self.wallet.wallet_account()
+	}
+}
+
+impl<W: WalletAccounts> WalletAccounts for StatefulWallet<W> {
+	fn wallet_accounts(&self) -> Vec<Self::Account> {
+		self.wallet.wallet_accounts()
+	}
+
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account> {
+		self.wallet.select_account(address)
+	}
+
+	fn sync_wallet_account(&mut self) -> Option<Self::Account> {
+		let account = self.wallet.sync_wallet_account();
+		self.sync_state().ok();
+
+		account
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletStandard> WalletStandardConnect for StatefulWallet<W> {
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>> {
+		self.connect_with_options(StandardConnectInput::default())
+			.await
+	}
+
+	/// A silent connection which returns no accounts moves back to
+	/// [`ConnectionState::Ready`].
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>> {
+		let pending = PendingTransition::start(&self.handle, ConnectionState::Connecting)?;
+		let result = self.wallet.connect_with_options(options).await;
+		let next = match &result {
+			Ok(_) if self.wallet.connected() => ConnectionState::Connected,
+			Ok(_) => ConnectionState::Ready,
+			Err(error) => self.failed_state(error),
+		};
+
+		pending.finish(next);
+
+		result
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletStandard> WalletStandardDisconnect for StatefulWallet<W> {
+	async fn disconnect(&mut self) -> WalletResult<()> {
+		let pending = PendingTransition::start(&self.handle, ConnectionState::Disconnecting)?;
+		let result = self.wallet.disconnect().await;
+		let next = match &result {
+			Ok(()) => ConnectionState::Ready,
+			Err(error) => self.failed_state(error),
+		};
+
+		pending.finish(next);
+
+		result
+	}
+}
+
+#[cfg(feature = "solana")]
+mod solana {
+	use async_trait::async_trait;
+
+	use super::StatefulWallet;
+	use crate::SolanaSignAndSendTransactionProps;
+	use crate::SolanaSignInInput;
+	use crate::SolanaSignTransactionProps;
+	use crate::WalletResult;
+	use crate::WalletSolanaSignAndSendTransaction;
+	use crate::WalletSolanaSignIn;
+	use crate::WalletSolanaSignMessage;
+	use crate::WalletSolanaSignTransaction;
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignMessage> WalletSolanaSignMessage for StatefulWallet<W> {
+		type Output = W::Output;
+
+		async fn sign_message_async(
+			&self,
+			message: impl Into<Vec<u8>>,
+		) -> WalletResult<Self::Output> {
+			self.wallet.sign_message_async(message).await
+		}
+
+		async fn sign_messages<M: Into<Vec<u8>>>(
+			&self,
+			messages: Vec<M>,
+		) -> WalletResult<Vec<Self::Output>> {
+			self.wallet.sign_messages(messages).await
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignTransaction> WalletSolanaSignTransaction for StatefulWallet<W> {
+		type Output = W::Output;
+
+		async fn sign_transaction(
+			&self,
+			props: SolanaSignTransactionProps,
+		) -> WalletResult<Self::Output> {
+			self.wallet.sign_transaction(props).await
+		}
+
+		async fn sign_transactions(
+			&self,
+			inputs: Vec<SolanaSignTransactionProps>,
+		) -> WalletResult<Vec<Self::Output>> {
+			self.wallet.sign_transactions(inputs).await
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignAndSendTransaction> WalletSolanaSignAndSendTransaction
+		for StatefulWallet<W>
+	{
+		type Output = W::Output;
+
+		async fn sign_and_send_transaction(
+			&self,
+			props: SolanaSignAndSendTransactionProps,
+		) -> WalletResult<Self::Output> {
+			self.wallet.sign_and_send_transaction(props).await
+		}
+
+		async fn sign_and_send_transactions(
+			&self,
+			inputs: Vec<SolanaSignAndSendTransactionProps>,
+		) -> WalletResult<Vec<Self::Output>> {
+			self.wallet.sign_and_send_transactions(inputs).await
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignIn> WalletSolanaSignIn for StatefulWallet<W> {
+		type Output = W::Output;
+
+		async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
+			self.wallet.sign_in(input).await
+		}
+
+		async fn sign_in_many(
+			&self,
+			inputs: Vec<SolanaSignInInput>,
+		) -> WalletResult<Vec<Self::Output>> {
+			self.wallet.sign_in_many(inputs).await
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rejects_illegal_transitions() {
+		let handle = ConnectionStateHandle::new(ConnectionState::Ready);
+
+		assert!(handle.transition(ConnectionState::Connecting).is_ok());
+		assert_eq!(
+			handle.transition(ConnectionState::Connecting),
+			Err(WalletError::InvalidConnectionTransition {
+				from: "connecting".into(),
+				to: "connecting".into(),
+			})
+		);
+		assert!(handle.transition(ConnectionState::Disconnecting).is_err());
+		assert_eq!(handle.state(), ConnectionState::Connecting);
+	}
+
+	#[test]
+	fn subscriptions_are_removed_on_drop() -> WalletResult<()> {
+		let handle = ConnectionStateHandle::new(ConnectionState::NotDetected);
+		let changes = Rc::new(RefCell::new(vec![]));
+		let subscription = handle.subscribe({
+			let changes = changes.clone();
+			move |change: &ConnectionStateChange| changes.borrow_mut().push(change.clone())
+		});
+
+		handle.transition(ConnectionState::Loadable)?;
+		drop(subscription);
+		handle.transition(ConnectionState::Ready)?;
+
+		assert_eq!(
+			*changes.borrow(),
+			[ConnectionStateChange {
+				previous: ConnectionState::NotDetected,
+				current: ConnectionState::Loadable,
+			}]
+		);
+
+		Ok(())
+	}
+
+	#[cfg(feature = "testing")]
+	mod wallet {
+
+		use futures::FutureExt;
+		use futures::executor::block_on;
+
+		use super::super::*;
+		use crate::testing::MockResponse;
+		use crate::testing::MockWallet;
+
+		fn record(
+			handle: &ConnectionStateHandle,
+		) -> (Rc<RefCell<Vec<ConnectionState>>>, ConnectionSubscription) {
+			let states = Rc::new(RefCell::new(vec![]));
+			let subscription = handle.subscribe({
+				let states = states.clone();
+				move |change| states.borrow_mut().push(change.current.clone())
+			});
+
+			(states, subscription)
+		}
+
+		#[test]
+		fn tracks_connection_lifecycle() -> WalletResult<()> {
+			let mut wallet = StatefulWallet::new(MockWallet::default());
+			let (states, _subscription) = record(&wallet.handle());
+
+			block_on(wallet.connect())?;
+			block_on(wallet.disconnect())?;
+
+			assert_eq!(
+				*states.borrow(),
+				[
+					ConnectionState::Connecting,
+					ConnectionState::Connected,
+					ConnectionState::Disconnecting,
+					ConnectionState::Ready,
+				]
+			);
+
+			Ok(())
+		}
+
+		#[test]
+		fn failed_connection_is_errored() {
+			let mut wallet = StatefulWallet::new(MockWallet::default());
+			wallet
+				.inner()
+				.handle()
+				.push_connect(MockResponse::Err(WalletError::WalletConnection));
+
+			assert!(block_on(wallet.connect()).is_err());
+			assert_eq!(
+				wallet.state(),
+				ConnectionState::Errored(WalletError::WalletConnection)
+			);
+
+			// The connection can be retried.
+			assert!(block_on(wallet.connect()).is_ok());
+			assert_eq!(wallet.state(), ConnectionState::Connected);
+		}
+
+		#[test]
+		fn failures_keep_a_connected_wallet_connected() -> WalletResult<()> {
+			let mut wallet = StatefulWallet::new(MockWallet::default());
+			block_on(wallet.connect())?;
+			wallet
+				.inner()
+				.handle()
+				.push_connect(MockResponse::Err(WalletError::WalletConnection))
+				.push_disconnect(MockResponse::Err(WalletError::WalletDisconnection));
+
+			assert!(block_on(wallet.connect()).is_err());
+			assert_eq!(wallet.state(), ConnectionState::Connected);
+			assert!(block_on(wallet.disconnect()).is_err());
+			assert_eq!(wallet.state(), ConnectionState::Connected);
+
+			Ok(())
+		}
+
+		#[test]
+		fn rejected_transitions_keep_the_wallet_result() {
+			let mut wallet = StatefulWallet::new(MockWallet::default());
+			let handle = wallet.handle();
+			let _subscription = handle.subscribe({
+				let handle = handle.clone();
+				move |change| {
+					// Move on before the connection finishes.
+					if change.current == ConnectionState::Connecting {
+						handle.transition(ConnectionState::Ready).ok();
+					}
+				}
+			});
+
+			assert!(block_on(wallet.connect()).is_ok());
+			assert!(wallet.inner().connected());
+		}
+
+		#[test]
+		fn cancelled_connection_restores_previous_state() {
+			let mut wallet = StatefulWallet::new(MockWallet::default());
+			wallet.inner().handle().push_connect(MockResponse::Pending);
+
+			assert!(wallet.connect().now_or_never().is_none());
+			assert_eq!(wallet.state(), ConnectionState::Ready);
+		}
+
+		#[test]
+		fn rejects_connecting_while_pending() {
+			let wallet = StatefulWallet::new(MockWallet::default());
+			let handle = wallet.handle();
+			let mut wallet = wallet;
+			handle.transition(ConnectionState::Connecting).unwrap();
+
+			assert!(matches!(
+				block_on(wallet.connect()),
+				Err(WalletError::InvalidConnectionTransition { .. })
+			));
+		}
+	}
+}
@@ -2,11 +2,38 @@ use std::fmt::Display;
 
 use serde::Deserialize;
 use serde::Serialize;
+use typed_builder::TypedBuilder;
 
+/// The errors produced by wallets.
+///
+/// Every variant has a stable numeric [`WalletError::code`]. The variants
+/// defined by [EIP-1193](https://eips.ethereum.org/EIPS/eip-1193#provider-errors),
+/// which are also used by Solana wallets, keep their standard code. Errors
+/// thrown by JS wallets are kept as [`WalletError::Js`] with all their details,
+/// and [`JsError::kind`] maps them to these variants from their `code` or
+/// `name`.
 #[derive(Debug, Clone, thiserror::Error, Eq, PartialEq, Serialize, Deserialize)]
 pub enum WalletError {
+	/// The user rejected the request.
+	#[error("The user rejected the request")]
+	UserRejected,
+	/// The requested method and/or account has not been authorized by the
+	/// user.
+	#[error("The requested method and/or account has not been authorized by the user")]
+	Unauthorized,
+	/// The wallet does not support the requested method.
+	#[error("The wallet does not support the requested method")]
+	UnsupportedMethod,
+	/// The wallet is disconnected from all chains.
+	#[error("The wallet is disconnected from all chains")]
+	Disconnected,
+	/// The wallet is not connected to the requested chain.
+	#[error("The wallet is not connected to the requested chain")]
+	ChainDisconnected,
 	#[error("the arguments provided are not valid")]
 	InvalidArguments,
+	#[error("the wallet connection can't move from `{from}` to `{to}`")]
+	InvalidConnectionTransition { from: String, to: String },
 	#[error("icon is not valid")]
 	InvalidIcon,
 	#[error("The identifier could not be parsed: {0}")]
@@ -15,8 +42,8 @@ pub enum WalletError {
 	InvalidSignature,
 	#[error("Signer: {0}")]
 	Signer(String),
-	#[error("{0}")]
-	Js(String),
+	#[error(transparent)]
+	Js(JsError),
 	#[error("Parsing string failed: {0}")]
 	ParseString(String),
 	#[error(transparent)]
@@ -29,12 +56,28 @@ pub enum WalletError {
 	Transaction(#[from] solana_transaction_error::TransactionError),
 	#[error("the requested feature: `{feature}` is not supported for this wallet: `{wallet}`")]
 	UnsupportedFeature { feature: String, wallet: String },
+	#[error(
+		"the requested feature: `{feature}` has version `{version}` for this wallet: `{wallet}`, \
+		 but `{required}` is required"
+	)]
+	UnsupportedFeatureVersion {
+		feature: String,
+		wallet: String,
+		version: String,
+		required: String,
+	},
+	#[error("The wallet returned {received} outputs for {expected} inputs")]
+	UnexpectedOutputCount { expected: usize, received: usize },
 	#[error("icon type is not supported")]
 	UnsupportedIconType,
 	#[error("The transaction version is not supported by this wallet")]
 	UnsupportedTransactionVersion,
 	#[error("Wallet account not connected")]
 	WalletAccount,
+	#[error("The wallet operation was cancelled")]
+	WalletCancelled,
+	#[error("The wallet account `{0}` is not authorized")]
+	WalletAccountNotFound(String),
 	#[error("The wallet configuration is invalid")]
 	WalletConfig,
 	#[error("An error occurred while connecting to the wallet")]
@@ -63,6 +106,12 @@ pub enum WalletError {
 	WalletSignIn,
 	#[error("Wallet sign in fields: {0}")]
 	WalletSignInFields(String),
+	#[cfg(feature = "solana")]
+	#[error(transparent)]
+	WalletSignInMessage(#[from] crate::SignInMessageError),
+	#[cfg(feature = "solana")]
+	#[error(transparent)]
+	WalletSignInVerification(#[from] crate::SignInVerificationError),
 	#[error("Wallet sign message")]
 	WalletSignMessage,
 	#[error("Wallet sign transaction")]
@@ -79,6 +128,140 @@ pub enum WalletError {
 	External(String),
 }
 
+impl WalletError {
+	/// The code of [`WalletError::ChainDisconnected`].
+	pub const CHAIN_DISCONNECTED: i32 = 4901;
+	/// The code of [`WalletError::Disconnected`].
+	pub const DISCONNECTED: i32 = 4900;
+	/// The code of [`WalletError::InvalidArguments`], matching the JSON-RPC
+	/// invalid params error.
+	pub const INVALID_ARGUMENTS: i32 = -32602;
+	/// The code of [`WalletError::Unauthorized`].
+	pub const UNAUTHORIZED: i32 = 4100;
+	/// The code of [`WalletError::UnsupportedMethod`].
+	pub const UNSUPPORTED_METHOD: i32 = 4200;
+	/// The code of [`WalletError::UserRejected`].
+	pub const USER_REJECTED: i32 = 4001;
+
+	/// A stable numeric code for the error, e.g. for telemetry.
+	///
+	/// Standard errors use their EIP-1193 code and the remaining errors use
+	/// codes from `5000`. Codes are never reused once they have been assigned.
+	pub const fn code(&self) -> i32 {
+		match self {
+			Self::UserRejected => Self::USER_REJECTED,
+			Self::Unauthorized => Self::UNAUTHORIZED,
+			Self::UnsupportedMethod => Self::UNSUPPORTED_METHOD,
+			Self::Disconnected => Self::DISCONNECTED,
+			Self::ChainDisconnected => Self::CHAIN_DISCONNECTED,
+			Self::InvalidArguments => Self::INVALID_ARGUMENTS,
+			Self::External(_) => 5000,
+			Self::InvalidConnectionTransition { .. } => 5001,
+			Self::InvalidIcon => 5002,
+			Self::InvalidIdentifier(_) => 5003,
+			Self::InvalidSignature => 5004,
+			Self::Signer(_) => 5005,
+			Self::Js(_) => 5006,
+			Self::ParseString(_) => 5007,
+			#[cfg(feature = "solana")]
+			Self::Program(_) => 5008,
+			Self::Serde(_) => 5009,
+			#[cfg(feature = "solana")]
+			Self::Transaction(_) => 5010,
+			Self::UnsupportedFeature { .. } => 5011,
+			Self::UnsupportedFeatureVersion { .. } => 5012,
+			Self::UnsupportedIconType => 5013,
+			Self::UnsupportedTransactionVersion => 5014,
+			Self::WalletAccount => 5015,
+			Self::WalletAccountNotFound(_) => 5016,
+			Self::WalletConfig => 5017,
+			Self::WalletConnection => 5018,
+			Self::WalletDecrypt => 5019,
+			Self::WalletDisconnected => 5020,
+			Self::WalletDisconnection => 5021,
+			Self::WalletEncrypt => 5022,
+			Self::WalletKeypair => 5023,
+			Self::WalletLoad => 5024,
+			Self::WalletNotConnected => 5025,
+			Self::WalletNotReady => 5026,
+			Self::WalletPublicKey => 5027,
+			Self::WalletSendTransaction => 5028,
+			Self::WalletSignIn => 5029,
+			Self::WalletSignInFields(_) => 5030,
+			Self::WalletSignMessage => 5031,
+			Self::WalletSignTransaction => 5032,
+			Self::WalletTimeout => 5033,
+			Self::WalletWindowBlocked => 5034,
+			Self::WalletWindowClosed => 5035,
+			Self::UnexpectedOutputCount { .. } => 5036,
+			Self::WalletCancelled => 5037,
+			#[cfg(feature = "solana")]
+			Self::WalletSignInMessage(_) => 5038,
+			#[cfg(feature = "solana")]
+			Self::WalletSignInVerification(_) => 5039,
+		}
+	}
+
+	/// The standard error with the provided EIP-1193 or JSON-RPC code.
+	pub const fn from_code(code: i32) -> Option<Self> {
+		match code {
+			Self::USER_REJECTED => Some(Self::UserRejected),
+			Self::UNAUTHORIZED => Some(Self::Unauthorized),
+			Self::UNSUPPORTED_METHOD => Some(Self::UnsupportedMethod),
+			Self::DISCONNECTED => Some(Self::Disconnected),
+			Self::CHAIN_DISCONNECTED => Some(Self::ChainDisconnected),
+			Self::INVALID_ARGUMENTS => Some(Self::InvalidArguments),
+			_ => None,
+		}
+	}
+
+	/// The error matching the `name` of a JS error, as thrown by wallets and
+	/// `@solana/wallet-adapter`.
+	pub fn from_name(name: &str) -> Option<Self> {
+		let error = match name {
+			"UserRejectedRequestError" | "UserRejectedError" | "WalletUserRejectedError" => {
+				Self::UserRejected
+			}
+			"UnauthorizedError" => Self::Unauthorized,
+			"UnsupportedMethodError" => Self::UnsupportedMethod,
+			"DisconnectedError" => Self::Disconnected,
+			"ChainDisconnectedError" => Self::ChainDisconnected,
+			"AbortError" => Self::WalletCancelled,
+			"WalletAccountError" => Self::WalletAccount,
+			"WalletConfigError" => Self::WalletConfig,
+			"WalletConnectionError" => Self::WalletConnection,
+			"WalletDisconnectedError" => Self::WalletDisconnected,
+			"WalletDisconnectionError" => Self::WalletDisconnection,
+			"WalletKeypairError" => Self::WalletKeypair,
+			"WalletLoadError" => Self::WalletLoad,
+			"WalletNotConnectedError" => Self::WalletNotConnected,
+			"WalletNotReadyError" => Self::WalletNotReady,
+			"WalletPublicKeyError" => Self::WalletPublicKey,
+			"WalletSendTransactionError" => Self::WalletSendTransaction,
+			"WalletSignInError" => Self::WalletSignIn,
+			"WalletSignMessageError" => Self::WalletSignMessage,
+			"WalletSignTransactionError" => Self::WalletSignTransaction,
+			"WalletTimeoutError" => Self::WalletTimeout,
+			"WalletWindowBlockedError" => Self::WalletWindowBlocked,
+			"WalletWindowClosedError" => Self::WalletWindowClosed,
+			_ => return None,
+		};
+
+		Some(error)
+	}
+
+	/// Whether the user rejected the request, as opposed to the request
+	/// failing. Errors thrown by JS wallets are checked by their
+	/// [`JsError::kind`].
+	pub fn is_user_rejected(&self) -> bool {
+		match self {
+			Self::UserRejected => true,
+			Self::Js(error) => error.kind() == Some(Self::UserRejected),
+			_ => false,
+		}
+	}
+}
+
 impl From<core::fmt::Error> for WalletError {
 	fn from(value: core::fmt::Error) -> Self {
 		WalletError::ParseString(value.to_string())
@@ -86,15 +269,112 @@ impl From<core::fmt::Error> for WalletError {
 }
 
 #[cfg(feature = "browser")]
-#[allow(unused_qualifications)]
 impl From<wasm_bindgen::JsValue> for WalletError {
-	#[allow(deprecated)]
 	fn from(source: wasm_bindgen::JsValue) -> Self {
-		WalletError::Js(
-			source
-				.as_string()
-				.unwrap_or("An error occurred in the JavaScript.".to_string()),
-		)
+		WalletError::Js(JsError::from(&source))
+	}
+}
+
+/// The details of a value thrown by JavaScript.
+///
+/// `Display` matches `Error.prototype.toString()`, e.g.
+/// `TypeError: Failed to fetch`, and the `cause` is exposed as the
+/// [`std::error::Error::source`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct JsError {
+	/// The `name` of the error, e.g. `TypeError`.
+	#[builder(default, setter(into, strip_option))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	/// The `message` of the error, or the thrown value when it is a string.
+	#[builder(setter(into))]
+	pub message: String,
+	/// The numeric `code` which wallets attach to their errors.
+	#[builder(default, setter(into, strip_option))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub code: Option<i32>,
+	/// The `stack` trace of the error.
+	#[builder(default, setter(into, strip_option))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub stack: Option<String>,
+	/// The error which caused this error.
+	#[builder(default, setter(transform = |cause: JsError| Some(Box::new(cause))))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cause: Option<Box<JsError>>,
+}
+
+impl JsError {
+	/// The message used when the thrown value doesn't have a message.
+	pub const DEFAULT_MESSAGE: &str = "An error occurred in the JavaScript.";
+	/// The number of nested causes which are read from a thrown value. This
+	/// guards against cycles.
+	#[cfg(feature = "browser")]
+	const MAX_CAUSE_DEPTH: usize = 8;
+
+	/// The [`WalletError`] variant which the error maps to from its `code`,
+	/// or from its `name` for wallet adapter errors like `WalletTimeoutError`.
+	pub fn kind(&self) -> Option<WalletError> {
+		self.code
+			.and_then(WalletError::from_code)
+			.or_else(|| self.name.as_deref().and_then(WalletError::from_name))
+	}
+
+	#[cfg(feature = "browser")]
+	fn read(value: &wasm_bindgen::JsValue, depth: usize) -> Self {
+		if let Some(message) = value.as_string() {
+			return Self::builder().message(message).build();
+		}
+
+		let property = |key: &str| {
+			js_sys::Reflect::get(value, &wasm_bindgen::JsValue::from_str(key))
+				.ok()
+				.filter(|value| !value.is_undefined() && !value.is_null())
+		};
+		let string = |key: &str| property(key).and_then(|value| value.as_string());
+
+		#[allow(clippy::cast_possible_truncation)]
+		let code = property("code")
+			.and_then(|code| code.as_f64())
+			.map(|code| code as i32);
+		let cause = property("cause")
+			.filter(|_| depth > 0)
+			.map(|cause| Box::new(Self::read(&cause, depth - 1)));
+
+		Self {
+			name: string("name"),
+			message: string("message").unwrap_or_else(|| Self::DEFAULT_MESSAGE.to_string()),
+			code,
+			stack: string("stack"),
+			cause,
+		}
+	}
+}
+
+#[cfg(feature = "browser")]
+impl From<&wasm_bindgen::JsValue> for JsError {
+	fn from(value: &wasm_bindgen::JsValue) -> Self {
+		Self::read(value, Self::MAX_CAUSE_DEPTH)
+	}
+}
+
+impl Display for JsError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.name.as_deref() {
+			Some(name) if !name.is_empty() && !self.message.is_empty() => {
+				write!(f, "{name}: {}", self.message)
+			}
+			Some(name) if !name.is_empty() => f.write_str(name),
+			_ => f.write_str(&self.message),
+		}
+	}
+}
+
+impl std::error::Error for JsError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		self.cause
+			.as_deref()
+			.map(|cause| cause as &(dyn std::error::Error + 'static))
 	}
 }
 #[cfg(feature = "solana")]
@@ -120,3 +400,85 @@ impl<E: IntoWalletError> From<E> for WalletError {
 		WalletError::External(value.to_string())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn standard_codes_round_trip() {
+		for error in [
+			WalletError::UserRejected,
+			WalletError::Unauthorized,
+			WalletError::UnsupportedMethod,
+			WalletError::Disconnected,
+			WalletError::ChainDisconnected,
+			WalletError::InvalidArguments,
+		] {
+			assert_eq!(WalletError::from_code(error.code()), Some(error));
+		}
+
+		assert_eq!(WalletError::UserRejected.code(), 4001);
+		assert_eq!(WalletError::ChainDisconnected.code(), 4901);
+		assert_eq!(WalletError::WalletTimeout.code(), 5033);
+		assert_eq!(WalletError::from_code(5033), None);
+	}
+
+	#[test]
+	fn errors_from_names() {
+		assert_eq!(
+			WalletError::from_name("UserRejectedRequestError"),
+			Some(WalletError::UserRejected)
+		);
+		assert_eq!(
+			WalletError::from_name("WalletWindowClosedError"),
+			Some(WalletError::WalletWindowClosed)
+		);
+		assert_eq!(WalletError::from_name("Error"), None);
+		assert!(WalletError::UserRejected.is_user_rejected());
+		assert!(!WalletError::WalletConnection.is_user_rejected());
+	}
+
+	#[test]
+	fn js_errors_map_to_a_kind() {
+		let rejected = WalletError::Js(JsError::builder().message("Rejected").code(4001).build());
+		let closed = JsError::builder()
+			.name("WalletWindowClosedError")
+			.message("The popup was closed")
+			.build();
+
+		assert!(rejected.is_user_rejected());
+		assert_eq!(closed.kind(), Some(WalletError::WalletWindowClosed));
+		assert!(!WalletError::Js(closed).is_user_rejected());
+		assert_eq!(JsError::builder().message("").build().kind(), None);
+	}
+
+	#[test]
+	fn js_errors_display_the_real_message() {
+		let cause = JsError::builder().message("Blockhash not found").build();
+		let error = WalletError::Js(
+			JsError::builder()
+				.name("WalletSendTransactionError")
+				.message("Transaction simulation failed")
+				.cause(cause.clone())
+				.build(),
+		);
+
+		assert_eq!(
+			error.to_string(),
+			"WalletSendTransactionError: Transaction simulation failed"
+		);
+		assert_eq!(
+			std::error::Error::source(&error).map(ToString::to_string),
+			Some(cause.to_string())
+		);
+		assert_eq!(
+			JsError::builder()
+				.message("")
+				.name("AbortError")
+				.build()
+				.to_string(),
+			"AbortError"
+		);
+	}
+}
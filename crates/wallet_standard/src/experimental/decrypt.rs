@@ -3,7 +3,9 @@ use serde::Deserialize;
 use serde::Serialize;
 use typed_builder::TypedBuilder;
 
+use crate::BatchResult;
 use crate::WalletResult;
+use crate::try_batch;
 
 pub const EXPERIMENTAL_DECRYPT: &str = "experimental:decrypt";
 
@@ -46,4 +48,13 @@ pub trait WalletExperimentalDecrypt {
 		props: Vec<ExperimentalDecryptProps>,
 	) -> WalletResult<Vec<Self::Output>>;
 	async fn decrypt(&self, props: ExperimentalDecryptProps) -> WalletResult<Self::Output>;
+
+	/// Decrypt each of the `props` in order, reporting the index of the first
+	/// failure along with the outputs which completed before it.
+	async fn decrypt_each(
+		&self,
+		props: Vec<ExperimentalDecryptProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(props, |props| self.decrypt(props)).await
+	}
 }
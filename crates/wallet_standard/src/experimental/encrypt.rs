@@ -3,7 +3,9 @@ use serde::Deserialize;
 use serde::Serialize;
 use typed_builder::TypedBuilder;
 
+use crate::BatchResult;
 use crate::WalletResult;
+use crate::try_batch;
 
 pub const EXPERIMENTAL_ENCRYPT: &str = "experimental:encrypt";
 
@@ -43,4 +45,13 @@ pub trait WalletExperimentalEncrypt {
 		props: Vec<ExperimentalEncryptProps>,
 	) -> WalletResult<Vec<Self::Output>>;
 	async fn encrypt(&self, props: ExperimentalEncryptProps) -> WalletResult<Self::Output>;
+
+	/// Encrypt each of the `props` in order, reporting the index of the first
+	/// failure along with the outputs which completed before it.
+	async fn encrypt_each(
+		&self,
+		props: Vec<ExperimentalEncryptProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(props, |props| self.encrypt(props)).await
+	}
 }
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::WalletError;
+use crate::WalletResult;
+
+const DATA_URL_PREFIX: &str = "data:";
+const BASE64_ENCODING: &str = "base64";
+
+/// The image types which the wallet standard allows for icons.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WalletIconMimeType {
+	#[serde(rename = "image/svg+xml")]
+	Svg,
+	#[serde(rename = "image/webp")]
+	Webp,
+	#[serde(rename = "image/png")]
+	Png,
+	#[serde(rename = "image/gif")]
+	Gif,
+}
+
+impl WalletIconMimeType {
+	pub const ALL: [Self; 4] = [Self::Svg, Self::Webp, Self::Png, Self::Gif];
+
+	/// The MIME type, e.g. `image/png`.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Svg => "image/svg+xml",
+			Self::Webp => "image/webp",
+			Self::Png => "image/png",
+			Self::Gif => "image/gif",
+		}
+	}
+
+	/// Whether the decoded payload looks like an image of this type.
+	fn matches(self, data: &[u8]) -> bool {
+		match self {
+			Self::Svg => std::str::from_utf8(data).is_ok_and(|text| text.contains("<svg")),
+			Self::Webp => data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP",
+			Self::Png => data.starts_with(b"\x89PNG\r\n\x1a\n"),
+			Self::Gif => data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a"),
+		}
+	}
+}
+
+impl fmt::Display for WalletIconMimeType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for WalletIconMimeType {
+	type Err = WalletError;
+
+	fn from_str(value: &str) -> WalletResult<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|mime_type| mime_type.as_str().eq_ignore_ascii_case(value))
+			.ok_or(WalletError::UnsupportedIconType)
+	}
+}
+
+/// A decoded wallet icon.
+///
+/// The wallet standard requires icons to be base64 encoded data URLs of an svg,
+/// webp, png or gif image. Parsing fails with [`WalletError::InvalidIcon`] when
+/// the value is not a base64 data URL or the payload can't be decoded, and with
+/// [`WalletError::UnsupportedIconType`] for any other type of data.
+///
+/// ```
+/// use wallet_standard::WalletIcon;
+/// use wallet_standard::WalletIconMimeType;
+///
+/// let icon = WalletIcon::parse("data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=")?;
+///
+/// assert_eq!(icon.mime_type(), WalletIconMimeType::Svg);
+/// assert_eq!(icon.data(), b"<svg></svg>");
+/// assert_eq!(icon.size(), 11);
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WalletIcon {
+	mime_type: WalletIconMimeType,
+	data: Vec<u8>,
+}
+
+impl WalletIcon {
+	/// Create an icon from decoded image data.
+	pub fn new(mime_type: WalletIconMimeType, data: impl Into<Vec<u8>>) -> WalletResult<Self> {
+		let data = data.into();
+
+		if !mime_type.matches(&data) {
+			return Err(WalletError::InvalidIcon);
+		}
+
+		Ok(Self { mime_type, data })
+	}
+
+	/// Parse a `data:image/{svg+xml,webp,png,gif};base64,...` URL.
+	pub fn parse(data_url: &str) -> WalletResult<Self> {
+		let (header, payload) = data_url
+			.trim()
+			.strip_prefix(DATA_URL_PREFIX)
+			.and_then(|rest| rest.split_once(','))
+			.ok_or(WalletError::InvalidIcon)?;
+		let (media_type, encoding) = header.split_once(';').unwrap_or((header, ""));
+
+		if !media_type.to_ascii_lowercase().starts_with("image/") {
+			return Err(WalletError::UnsupportedIconType);
+		}
+
+		let mime_type = media_type.parse::<WalletIconMimeType>()?;
+
+		if !encoding.eq_ignore_ascii_case(BASE64_ENCODING) {
+			return Err(WalletError::InvalidIcon);
+		}
+
+		let data = BASE64
+			.decode(payload)
+			.map_err(|_| WalletError::InvalidIcon)?;
+
+		Self::new(mime_type, data)
+	}
+
+	pub fn mime_type(&self) -> WalletIconMimeType {
+		self.mime_type
+	}
+
+	/// The decoded image data.
+	pub fn data(&self) -> &[u8] {
+		&self.data
+	}
+
+	/// The size of the decoded image data in bytes.
+	pub fn size(&self) -> usize {
+		self.data.len()
+	}
+
+	/// Encode the icon as a base64 data URL.
+	pub fn to_data_url(&self) -> String {
+		format!(
+			"{DATA_URL_PREFIX}{};{BASE64_ENCODING},{}",
+			self.mime_type,
+			BASE64.encode(&self.data)
+		)
+	}
+}
+
+impl fmt::Display for WalletIcon {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.to_data_url())
+	}
+}
+
+impl FromStr for WalletIcon {
+	type Err = WalletError;
+
+	fn from_str(value: &str) -> WalletResult<Self> {
+		Self::parse(value)
+	}
+}
+
+impl TryFrom<&str> for WalletIcon {
+	type Error = WalletError;
+
+	fn try_from(value: &str) -> WalletResult<Self> {
+		Self::parse(value)
+	}
+}
+
+impl TryFrom<String> for WalletIcon {
+	type Error = WalletError;
+
+	fn try_from(value: String) -> WalletResult<Self> {
+		Self::parse(&value)
+	}
+}
+
+impl From<WalletIcon> for String {
+	fn from(value: WalletIcon) -> Self {
+		value.to_data_url()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+	#[test]
+	fn parse_supported_icons() -> WalletResult<()> {
+		let png = format!("data:image/png;base64,{}", BASE64.encode(PNG));
+		let icon = WalletIcon::parse(&png)?;
+
+		assert_eq!(icon.mime_type(), WalletIconMimeType::Png);
+		assert_eq!(icon.data(), PNG);
+		assert_eq!(icon.size(), PNG.len());
+		assert_eq!(icon.to_data_url(), png);
+
+		let gif = format!("data:image/gif;base64,{}", BASE64.encode(b"GIF89a\x01\0"));
+		assert_eq!(
+			WalletIcon::parse(&gif)?.mime_type(),
+			WalletIconMimeType::Gif
+		);
+
+		let webp = format!(
+			"data:image/webp;base64,{}",
+			BASE64.encode(b"RIFF\x1a\0\0\0WEBPVP8 ")
+		);
+		assert_eq!(
+			WalletIcon::parse(&webp)?.mime_type(),
+			WalletIconMimeType::Webp
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn reject_invalid_icons() {
+		for invalid in [
+			"",
+			"https://example.com/icon.png",
+			"data:image/png;base64",
+			"data:image/svg+xml,<svg></svg>",
+			"data:image/svg+xml;base64,not base64!",
+			"data:image/svg+xml;base64,",
+			// A png payload with an svg MIME type.
+			"data:image/svg+xml;base64,iVBORw0KGgo=",
+		] {
+			assert_eq!(
+				WalletIcon::parse(invalid),
+				Err(WalletError::InvalidIcon),
+				"{invalid}"
+			);
+		}
+	}
+
+	#[test]
+	fn reject_unsupported_icon_types() {
+		for unsupported in [
+			"data:image/jpeg;base64,/9j/4AAQ",
+			"data:text/html;base64,PHNjcmlwdD4=",
+		] {
+			assert_eq!(
+				WalletIcon::parse(unsupported),
+				Err(WalletError::UnsupportedIconType),
+				"{unsupported}"
+			);
+		}
+	}
+}
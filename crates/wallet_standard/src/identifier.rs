@@ -0,0 +1,414 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::WalletError;
+use crate::WalletResult;
+
+/// The namespace reserved by the wallet standard for standard features.
+pub const STANDARD_NAMESPACE: &str = "standard";
+/// The namespace reserved by the wallet standard for experimental features.
+pub const EXPERIMENTAL_NAMESPACE: &str = "experimental";
+
+/// A validated `namespace:reference` identifier.
+///
+/// Identifiers are used by the wallet standard for both chains and features.
+/// The namespace is everything before the first `:` and the reference is
+/// everything after it. Neither may be empty or contain whitespace.
+///
+/// ```
+/// use wallet_standard::IdentifierString;
+///
+/// let identifier: IdentifierString = "solana:signAndSendTransaction".parse()?;
+///
+/// assert_eq!(identifier.namespace(), "solana");
+/// assert_eq!(identifier.reference(), "signAndSendTransaction");
+/// assert!("solana".parse::<IdentifierString>().is_err());
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IdentifierString {
+	value: Cow<'static, str>,
+	separator: usize,
+}
+
+impl IdentifierString {
+	/// Parse an identifier.
+	pub fn parse(value: impl Into<String>) -> WalletResult<Self> {
+		let value = value.into();
+
+		match find_separator(value.as_bytes()) {
+			Ok(separator) => {
+				Ok(Self {
+					value: Cow::Owned(value),
+					separator,
+				})
+			}
+			Err(reason) => Err(invalid_identifier(&value, reason)),
+		}
+	}
+
+	/// Create an identifier from a static string in a const context.
+	///
+	/// # Panics
+	///
+	/// Panics when the value is not a valid identifier. In a const context
+	/// this is a compile time error.
+	pub const fn from_static(value: &'static str) -> Self {
+		match find_separator(value.as_bytes()) {
+			Ok(separator) => {
+				Self {
+					value: Cow::Borrowed(value),
+					separator,
+				}
+			}
+			Err(reason) => panic!("{}", reason),
+		}
+	}
+
+	/// The full identifier.
+	pub fn as_str(&self) -> &str {
+		&self.value
+	}
+
+	/// The part of the identifier before the first `:`.
+	pub fn namespace(&self) -> &str {
+		&self.value[..self.separator]
+	}
+
+	/// The part of the identifier after the first `:`.
+	pub fn reference(&self) -> &str {
+		&self.value[self.separator + 1..]
+	}
+
+	/// Whether the namespace is `standard`.
+	pub fn is_standard(&self) -> bool {
+		self.namespace() == STANDARD_NAMESPACE
+	}
+
+	/// Whether the namespace is `experimental`.
+	pub fn is_experimental(&self) -> bool {
+		self.namespace() == EXPERIMENTAL_NAMESPACE
+	}
+
+	/// Whether the namespace is reserved by the wallet standard.
+	pub fn is_reserved(&self) -> bool {
+		self.is_standard() || self.is_experimental()
+	}
+}
+
+/// Find the index of the first `:` in the identifier. This is a `const fn` so
+/// that it can validate identifiers created with `from_static`.
+const fn find_separator(bytes: &[u8]) -> Result<usize, &'static str> {
+	let mut separator = None;
+	let mut index = 0;
+
+	while index < bytes.len() {
+		let byte = bytes[index];
+
+		if byte.is_ascii_whitespace() || byte.is_ascii_control() {
+			return Err("identifiers can't contain whitespace or control characters");
+		}
+
+		if byte == b':' && separator.is_none() {
+			separator = Some(index);
+		}
+
+		index += 1;
+	}
+
+	match separator {
+		None => Err("expected an identifier in the form `namespace:reference`"),
+		Some(0) => Err("the namespace of an identifier can't be empty"),
+		Some(separator) if separator + 1 == bytes.len() => {
+			Err("the reference of an identifier can't be empty")
+		}
+		Some(separator) => Ok(separator),
+	}
+}
+
+const fn bytes_eq(left: &[u8], right: &[u8]) -> bool {
+	if left.len() != right.len() {
+		return false;
+	}
+
+	let mut index = 0;
+
+	while index < left.len() {
+		if left[index] != right[index] {
+			return false;
+		}
+
+		index += 1;
+	}
+
+	true
+}
+
+fn invalid_identifier(value: &str, reason: &str) -> WalletError {
+	WalletError::InvalidIdentifier(format!("`{value}`: {reason}"))
+}
+
+/// A chain supported by a wallet or account, such as `solana:mainnet`.
+///
+/// Chains can't use the `standard` or `experimental` namespaces which are
+/// reserved by the wallet standard.
+///
+/// ```
+/// use wallet_standard::ChainId;
+///
+/// let chain: ChainId = "solana:devnet".parse()?;
+///
+/// assert_eq!(chain.namespace(), "solana");
+/// assert!("standard:connect".parse::<ChainId>().is_err());
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ChainId(IdentifierString);
+
+impl ChainId {
+	/// Parse a chain.
+	pub fn parse(value: impl Into<String>) -> WalletResult<Self> {
+		Self::try_from(IdentifierString::parse(value)?)
+	}
+
+	/// Create a chain from a static string in a const context.
+	///
+	/// # Panics
+	///
+	/// Panics when the value is not a valid identifier or uses a reserved
+	/// namespace.
+	pub const fn from_static(value: &'static str) -> Self {
+		let identifier = IdentifierString::from_static(value);
+		let namespace = value.as_bytes().split_at(identifier.separator).0;
+
+		assert!(
+			!bytes_eq(namespace, STANDARD_NAMESPACE.as_bytes())
+				&& !bytes_eq(namespace, EXPERIMENTAL_NAMESPACE.as_bytes()),
+			"chains can't use a namespace reserved by the wallet standard"
+		);
+
+		Self(identifier)
+	}
+}
+
+impl TryFrom<IdentifierString> for ChainId {
+	type Error = WalletError;
+
+	fn try_from(identifier: IdentifierString) -> WalletResult<Self> {
+		if identifier.is_reserved() {
+			return Err(invalid_identifier(
+				identifier.as_str(),
+				"chains can't use a namespace reserved by the wallet standard",
+			));
+		}
+
+		Ok(Self(identifier))
+	}
+}
+
+/// The name of a feature supported by a wallet or account, such as
+/// `standard:connect` or `solana:signMessage`.
+///
+/// ```
+/// use wallet_standard::FeatureName;
+/// use wallet_standard::STANDARD_CONNECT;
+///
+/// let feature: FeatureName = STANDARD_CONNECT.parse()?;
+///
+/// assert!(feature.is_standard());
+/// assert_eq!(feature, STANDARD_CONNECT);
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FeatureName(IdentifierString);
+
+impl FeatureName {
+	/// Parse a feature name.
+	pub fn parse(value: impl Into<String>) -> WalletResult<Self> {
+		IdentifierString::parse(value).map(Self)
+	}
+
+	/// Create a feature name from a static string in a const context.
+	///
+	/// # Panics
+	///
+	/// Panics when the value is not a valid identifier.
+	pub const fn from_static(value: &'static str) -> Self {
+		Self(IdentifierString::from_static(value))
+	}
+}
+
+impl From<IdentifierString> for FeatureName {
+	fn from(identifier: IdentifierString) -> Self {
+		Self(identifier)
+	}
+}
+
+macro_rules! identifier_newtype {
+	($name:ident) => {
+		impl Deref for $name {
+			type Target = IdentifierString;
+
+			fn deref(&self) -> &Self::Target {
+				&self.0
+			}
+		}
+
+		impl From<$name> for IdentifierString {
+			fn from(value: $name) -> Self {
+				value.0
+			}
+		}
+	};
+}
+
+identifier_newtype!(ChainId);
+identifier_newtype!(FeatureName);
+
+macro_rules! identifier_conversions {
+	($name:ident) => {
+		impl FromStr for $name {
+			type Err = WalletError;
+
+			fn from_str(value: &str) -> WalletResult<Self> {
+				Self::parse(value)
+			}
+		}
+
+		impl TryFrom<String> for $name {
+			type Error = WalletError;
+
+			fn try_from(value: String) -> WalletResult<Self> {
+				Self::parse(value)
+			}
+		}
+
+		impl TryFrom<&str> for $name {
+			type Error = WalletError;
+
+			fn try_from(value: &str) -> WalletResult<Self> {
+				Self::parse(value)
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(value: $name) -> Self {
+				value.as_str().to_string()
+			}
+		}
+
+		impl AsRef<str> for $name {
+			fn as_ref(&self) -> &str {
+				self.as_str()
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				f.write_str(self.as_str())
+			}
+		}
+
+		impl PartialEq<str> for $name {
+			fn eq(&self, other: &str) -> bool {
+				self.as_str() == other
+			}
+		}
+
+		impl PartialEq<&str> for $name {
+			fn eq(&self, other: &&str) -> bool {
+				self.as_str() == *other
+			}
+		}
+
+		impl PartialEq<String> for $name {
+			fn eq(&self, other: &String) -> bool {
+				self.as_str() == other
+			}
+		}
+	};
+}
+
+identifier_conversions!(IdentifierString);
+identifier_conversions!(ChainId);
+identifier_conversions!(FeatureName);
+
+/// Parse every value into the identifier type, failing on the first invalid
+/// value.
+pub(crate) fn parse_all<T: FromStr<Err = WalletError>>(values: &[String]) -> WalletResult<Vec<T>> {
+	values.iter().map(|value| value.parse()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::STANDARD_CONNECT;
+
+	#[test]
+	fn parse_identifiers() -> WalletResult<()> {
+		let identifier = IdentifierString::parse("solana:mainnet:extra")?;
+
+		assert_eq!(identifier.namespace(), "solana");
+		assert_eq!(identifier.reference(), "mainnet:extra");
+		assert_eq!(identifier.to_string(), "solana:mainnet:extra");
+
+		for invalid in ["", "solana", ":mainnet", "solana:", "solana: mainnet"] {
+			assert!(
+				matches!(
+					IdentifierString::parse(invalid),
+					Err(WalletError::InvalidIdentifier(_))
+				),
+				"{invalid}"
+			);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn chains_reject_reserved_namespaces() {
+		assert!(ChainId::parse("solana:mainnet").is_ok());
+		assert!(ChainId::parse("standard:connect").is_err());
+		assert!(ChainId::parse("experimental:encrypt").is_err());
+		assert!(
+			FeatureName::parse("experimental:encrypt").is_ok_and(|feature| feature.is_reserved())
+		);
+	}
+
+	#[test]
+	fn from_static_matches_parse() -> WalletResult<()> {
+		const CONNECT: FeatureName = FeatureName::from_static(STANDARD_CONNECT);
+		const MAINNET: ChainId = ChainId::from_static("solana:mainnet");
+
+		assert_eq!(CONNECT, FeatureName::parse(STANDARD_CONNECT)?);
+		assert_eq!(MAINNET, ChainId::parse("solana:mainnet")?);
+
+		Ok(())
+	}
+
+	#[test]
+	#[should_panic(expected = "reserved")]
+	fn from_static_panics_for_reserved_chains() {
+		let _ = ChainId::from_static("standard:mainnet");
+	}
+
+	#[test]
+	fn serde_uses_strings() -> Result<(), serde_json::Error> {
+		let chain: ChainId = serde_json::from_str("\"solana:devnet\"")?;
+
+		assert_eq!(chain, "solana:devnet");
+		assert_eq!(serde_json::to_string(&chain)?, "\"solana:devnet\"");
+		assert!(serde_json::from_str::<ChainId>("\"standard:devnet\"").is_err());
+		assert!(serde_json::from_str::<FeatureName>("\"connect\"").is_err());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use log::Level;
+
+use super::Next;
+use super::WalletLayer;
+use super::WalletRequest;
+use crate::WalletResult;
+
+/// Logs every request and its outcome with the [`log`] crate.
+///
+/// Requests and successes are logged at the configured level, while failures
+/// are logged as warnings. Only the method and the number of items are
+/// logged, never the messages or transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoggingLayer {
+	level: Level,
+	target: &'static str,
+}
+
+impl LoggingLayer {
+	pub const fn new(level: Level) -> Self {
+		Self {
+			level,
+			target: "wallet_standard",
+		}
+	}
+
+	/// Log with the provided target instead of `wallet_standard`.
+	#[must_use]
+	pub const fn target(mut self, target: &'static str) -> Self {
+		self.target = target;
+		self
+	}
+}
+
+impl Default for LoggingLayer {
+	fn default() -> Self {
+		Self::new(Level::Debug)
+	}
+}
+
+#[async_trait(?Send)]
+impl WalletLayer for LoggingLayer {
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+		let description = request.to_string();
+		log::log!(target: self.target, self.level, "{description}: started");
+
+		let result = next.run(request).await;
+
+		match &result {
+			Ok(_) => log::log!(target: self.target, self.level, "{description}: succeeded"),
+			Err(error) => {
+				log::warn!(target: self.target, "{description}: failed ({}): {error}", error.code());
+			}
+		}
+
+		result
+	}
+}
@@ -0,0 +1,347 @@
+use async_trait::async_trait;
+use futures::future::LocalBoxFuture;
+pub use logging::*;
+pub use request::*;
+pub use retry::*;
+pub use timing::*;
+pub use validation::*;
+pub use wallet::*;
+
+use crate::WalletResult;
+
+mod logging;
+mod request;
+mod retry;
+mod timing;
+mod validation;
+mod wallet;
+
+/// Intercepts the requests made to a [`LayeredWallet`], in the spirit of
+/// `tower`.
+///
+/// A layer receives every feature call as a [`WalletRequest`] and decides how
+/// to run it through [`Next`]. Layers are written once and work with any
+/// wallet, including `BrowserWallet`. The output type `T` depends on the
+/// request, so layers can inspect and replace the request and observe whether
+/// the call succeeded, but can't inspect successful outputs.
+///
+/// ```rust
+/// # #[cfg(feature = "solana")]
+/// # {
+/// use solana_keypair::Keypair;
+/// use wallet_standard::KeypairWallet;
+/// use wallet_standard::LayeredWallet;
+/// use wallet_standard::LoggingLayer;
+/// use wallet_standard::RetryLayer;
+/// use wallet_standard::ValidationLayer;
+/// use wallet_standard::prelude::*;
+///
+/// # futures::executor::block_on(async {
+/// let mut wallet = LayeredWallet::new(KeypairWallet::new(Keypair::new()), RetryLayer::new(3))
+/// 	.with(ValidationLayer::new())
+/// 	.with(LoggingLayer::default());
+///
+/// wallet.connect().await?;
+/// wallet.sign_message_async("hello").await?;
+/// assert_eq!(
+/// 	wallet.sign_messages(Vec::<Vec<u8>>::new()).await,
+/// 	Err(WalletError::InvalidArguments)
+/// );
+/// # WalletResult::Ok(())
+/// # })
+/// # .unwrap();
+/// # }
+/// ```
+#[async_trait(?Send)]
+pub trait WalletLayer {
+	/// Handle the `request`, usually by running it with `next`.
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T>;
+}
+
+/// The rest of the layers and the wallet.
+///
+/// `next` can be run several times, e.g. to retry a request. Requests which
+/// take `&mut self`, like `connect`, must be run one at a time.
+pub struct Next<'a, T> {
+	run: &'a dyn Fn(WalletRequest) -> LocalBoxFuture<'a, WalletResult<T>>,
+}
+
+impl<'a, T> Next<'a, T> {
+	pub fn new(run: &'a dyn Fn(WalletRequest) -> LocalBoxFuture<'a, WalletResult<T>>) -> Self {
+		Self { run }
+	}
+
+	/// Run the request with the remaining layers.
+	pub fn run(&self, request: WalletRequest) -> LocalBoxFuture<'a, WalletResult<T>> {
+		(self.run)(request)
+	}
+}
+
+impl<T> Clone for Next<'_, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for Next<'_, T> {}
+
+impl<T> std::fmt::Debug for Next<'_, T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Next").finish_non_exhaustive()
+	}
+}
+
+/// The layer which runs every request unchanged.
+#[async_trait(?Send)]
+impl WalletLayer for () {
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+		next.run(request).await
+	}
+}
+
+/// Two layers where `outer` handles each request before `inner`.
+#[derive(Clone, Debug, Default)]
+pub struct WalletLayerStack<Inner, Outer> {
+	inner: Inner,
+	outer: Outer,
+}
+
+impl<Inner, Outer> WalletLayerStack<Inner, Outer> {
+	pub fn new(inner: Inner, outer: Outer) -> Self {
+		Self { inner, outer }
+	}
+
+	pub fn inner(&self) -> &Inner {
+		&self.inner
+	}
+
+	pub fn outer(&self) -> &Outer {
+		&self.outer
+	}
+}
+
+#[async_trait(?Send)]
+impl<Inner: WalletLayer, Outer: WalletLayer> WalletLayer for WalletLayerStack<Inner, Outer> {
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+		let inner = |request| self.inner.call(request, next);
+
+		self.outer.call(request, Next::new(&inner)).await
+	}
+}
+
+#[cfg(feature = "testing")]
+#[cfg(test)]
+mod tests {
+	use std::cell::Cell;
+	use std::cell::RefCell;
+	use std::rc::Rc;
+	use std::time::Duration;
+
+	use futures::executor::block_on;
+	use solana_message::Message;
+	use solana_transaction::Transaction;
+	use solana_transaction::versioned::VersionedTransaction;
+
+	use super::*;
+	use crate::JsError;
+	use crate::SolanaSignAndSendTransactionProps;
+	use crate::WalletError;
+	use crate::WalletSolanaSignAndSendTransaction;
+	use crate::WalletSolanaSignMessage;
+	use crate::WalletStandardConnect;
+	use crate::testing::MockCall;
+	use crate::testing::MockWallet;
+
+	/// Records the requests it handles under its name.
+	struct Recorder(&'static str, Rc<RefCell<Vec<String>>>);
+
+	#[async_trait(?Send)]
+	impl WalletLayer for Recorder {
+		async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+			self.1.borrow_mut().push(format!("{}: {request}", self.0));
+			next.run(request).await
+		}
+	}
+
+	#[test]
+	fn later_layers_run_first() -> WalletResult<()> {
+		let log = Rc::new(RefCell::new(vec![]));
+		let mut wallet = LayeredWallet::new(MockWallet::default(), Recorder("inner", log.clone()))
+			.with(Recorder("outer", log.clone()));
+
+		block_on(wallet.connect())?;
+		block_on(wallet.sign_messages(vec!["a", "b"]))?;
+
+		assert_eq!(
+			*log.borrow(),
+			[
+				"outer: connect",
+				"inner: connect",
+				"outer: sign_messages (2 items)",
+				"inner: sign_messages (2 items)",
+			]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn retries_idempotent_requests() -> WalletResult<()> {
+		let mock = MockWallet::default();
+		let handle = mock.handle();
+		let mut wallet = LayeredWallet::new(mock, RetryLayer::new(3).retry_prompts(true));
+		block_on(wallet.connect())?;
+		handle.clear_calls();
+
+		handle
+			.push_sign_message(Err(WalletError::WalletNotReady))
+			.push_sign_message(Err(WalletError::WalletConnection));
+		block_on(wallet.sign_message_async("hello"))?;
+		assert_eq!(handle.calls().len(), 3);
+
+		handle.clear_calls();
+		handle.push_sign_message(Err(WalletError::UserRejected));
+		assert_eq!(
+			block_on(wallet.sign_message_async("hello")),
+			Err(WalletError::UserRejected)
+		);
+		assert_eq!(handle.calls().len(), 1);
+
+		handle.clear_calls();
+		handle.push_sign_and_send_transaction(Err(WalletError::WalletNotReady));
+		let props = SolanaSignAndSendTransactionProps::builder()
+			.transaction(VersionedTransaction::from(Transaction::new_unsigned(
+				Message::new(&[], None),
+			)))
+			.build();
+		assert_eq!(
+			block_on(wallet.sign_and_send_transaction(props.clone())),
+			Err(WalletError::WalletNotReady)
+		);
+		assert_eq!(handle.calls(), [MockCall::SignAndSendTransaction(props)]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn closed_windows_and_prompts_are_not_retried() -> WalletResult<()> {
+		for error in [
+			WalletError::WalletWindowClosed,
+			WalletError::WalletWindowBlocked,
+			WalletError::WalletTimeout,
+			WalletError::Js(
+				JsError::builder()
+					.name("WalletWindowClosedError")
+					.message("The popup was closed")
+					.build(),
+			),
+		] {
+			let mock = MockWallet::default();
+			let handle = mock.handle();
+			let mut wallet = LayeredWallet::new(mock, RetryLayer::new(3).retry_prompts(true));
+
+			handle.push_connect(Err(error.clone()));
+			assert_eq!(block_on(wallet.connect()), Err(error));
+			assert_eq!(handle.calls().len(), 1);
+		}
+
+		let mock = MockWallet::default();
+		let handle = mock.handle();
+		let mut wallet = LayeredWallet::new(mock, RetryLayer::new(3));
+		block_on(wallet.connect())?;
+		handle.clear_calls();
+
+		handle.push_sign_message(Err(WalletError::WalletNotReady));
+		assert_eq!(
+			block_on(wallet.sign_message_async("hello")),
+			Err(WalletError::WalletNotReady)
+		);
+		assert_eq!(handle.calls().len(), 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn validation_rejects_requests_before_the_wallet() -> WalletResult<()> {
+		let mock = MockWallet::default();
+		let handle = mock.handle();
+		let layer = ValidationLayer::new().rule(|request| {
+			match request {
+				WalletRequest::SignMessage(message) if message.len() > 4 => {
+					Err(WalletError::InvalidArguments)
+				}
+				_ => Ok(()),
+			}
+		});
+		let mut wallet = LayeredWallet::new(mock, layer);
+		block_on(wallet.connect())?;
+		handle.clear_calls();
+
+		assert_eq!(
+			block_on(wallet.sign_messages(Vec::<Vec<u8>>::new())),
+			Err(WalletError::InvalidArguments)
+		);
+		assert_eq!(
+			block_on(wallet.sign_message_async("too long")),
+			Err(WalletError::InvalidArguments)
+		);
+		assert!(handle.calls().is_empty());
+		block_on(wallet.sign_message_async("ok"))?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn timing_reports_every_request() -> WalletResult<()> {
+		let now = Rc::new(Cell::new(Duration::ZERO));
+		let timings = Rc::new(RefCell::new(vec![]));
+		let layer = TimingLayer::new({
+			let timings = timings.clone();
+			move |timing: &WalletTiming| timings.borrow_mut().push(*timing)
+		})
+		.clock({
+			let now = now.clone();
+			move || {
+				let current = now.get();
+				now.set(current + Duration::from_millis(5));
+				current
+			}
+		});
+		let mock = MockWallet::default();
+		mock.handle()
+			.push_sign_message(Err(WalletError::UserRejected));
+		let mut wallet = LayeredWallet::new(mock, layer);
+
+		block_on(wallet.connect())?;
+		block_on(wallet.sign_message_async("hello")).ok();
+
+		let timings = timings.borrow();
+		assert_eq!(timings.len(), 2);
+		assert_eq!(timings[0].feature, crate::STANDARD_CONNECT);
+		assert_eq!(timings[0].duration, Duration::from_millis(5));
+		assert_eq!(timings[0].error_code, None);
+		assert_eq!(timings[1].method, "sign_message");
+		assert_eq!(timings[1].error_code, Some(WalletError::USER_REJECTED));
+
+		Ok(())
+	}
+
+	#[test]
+	fn replacing_the_request_variant_fails() {
+		struct Replace;
+
+		#[async_trait(?Send)]
+		impl WalletLayer for Replace {
+			async fn call<T>(&self, _: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+				next.run(WalletRequest::Disconnect).await
+			}
+		}
+
+		let mut wallet = LayeredWallet::new(MockWallet::default(), Replace);
+
+		assert_eq!(
+			block_on(wallet.connect()),
+			Err(WalletError::InvalidArguments)
+		);
+	}
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::EXPERIMENTAL_DECRYPT;
+use crate::EXPERIMENTAL_ENCRYPT;
+use crate::ExperimentalDecryptProps;
+use crate::ExperimentalEncryptProps;
+#[cfg(feature = "solana")]
+use crate::SOLANA_SIGN_AND_SEND_TRANSACTION;
+#[cfg(feature = "solana")]
+use crate::SOLANA_SIGN_IN;
+#[cfg(feature = "solana")]
+use crate::SOLANA_SIGN_MESSAGE;
+#[cfg(feature = "solana")]
+use crate::SOLANA_SIGN_TRANSACTION;
+use crate::STANDARD_CONNECT;
+use crate::STANDARD_DISCONNECT;
+#[cfg(feature = "solana")]
+use crate::SolanaSignAndSendTransactionProps;
+#[cfg(feature = "solana")]
+use crate::SolanaSignInInput;
+#[cfg(feature = "solana")]
+use crate::SolanaSignTransactionProps;
+use crate::StandardConnectInput;
+
+/// A feature call made to a [`super::LayeredWallet`], along with its inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WalletRequest {
+	Connect(StandardConnectInput),
+	Disconnect,
+	Encrypt(ExperimentalEncryptProps),
+	EncryptMany(Vec<ExperimentalEncryptProps>),
+	Decrypt(ExperimentalDecryptProps),
+	DecryptMany(Vec<ExperimentalDecryptProps>),
+	#[cfg(feature = "solana")]
+	SignMessage(Vec<u8>),
+	#[cfg(feature = "solana")]
+	SignMessages(Vec<Vec<u8>>),
+	#[cfg(feature = "solana")]
+	SignTransaction(SolanaSignTransactionProps),
+	#[cfg(feature = "solana")]
+	SignTransactions(Vec<SolanaSignTransactionProps>),
+	#[cfg(feature = "solana")]
+	SignAndSendTransaction(SolanaSignAndSendTransactionProps),
+	#[cfg(feature = "solana")]
+	SignAndSendTransactions(Vec<SolanaSignAndSendTransactionProps>),
+	#[cfg(feature = "solana")]
+	SignIn(SolanaSignInInput),
+	#[cfg(feature = "solana")]
+	SignInMany(Vec<SolanaSignInInput>),
+}
+
+impl WalletRequest {
+	/// The name of the wallet method, e.g. `sign_transactions`.
+	pub const fn method(&self) -> &'static str {
+		match self {
+			Self::Connect(_) => "connect",
+			Self::Disconnect => "disconnect",
+			Self::Encrypt(_) => "encrypt",
+			Self::EncryptMany(_) => "encrypt_many",
+			Self::Decrypt(_) => "decrypt",
+			Self::DecryptMany(_) => "decrypt_many",
+			#[cfg(feature = "solana")]
+			Self::SignMessage(_) => "sign_message",
+			#[cfg(feature = "solana")]
+			Self::SignMessages(_) => "sign_messages",
+			#[cfg(feature = "solana")]
+			Self::SignTransaction(_) => "sign_transaction",
+			#[cfg(feature = "solana")]
+			Self::SignTransactions(_) => "sign_transactions",
+			#[cfg(feature = "solana")]
+			Self::SignAndSendTransaction(_) => "sign_and_send_transaction",
+			#[cfg(feature = "solana")]
+			Self::SignAndSendTransactions(_) => "sign_and_send_transactions",
+			#[cfg(feature = "solana")]
+			Self::SignIn(_) => "sign_in",
+			#[cfg(feature = "solana")]
+			Self::SignInMany(_) => "sign_in_many",
+		}
+	}
+
+	/// The identifier of the wallet feature which handles the request, e.g.
+	/// `solana:signTransaction`.
+	pub const fn feature(&self) -> &'static str {
+		match self {
+			Self::Connect(_) => STANDARD_CONNECT,
+			Self::Disconnect => STANDARD_DISCONNECT,
+			Self::Encrypt(_) | Self::EncryptMany(_) => EXPERIMENTAL_ENCRYPT,
+			Self::Decrypt(_) | Self::DecryptMany(_) => EXPERIMENTAL_DECRYPT,
+			#[cfg(feature = "solana")]
+			Self::SignMessage(_) | Self::SignMessages(_) => SOLANA_SIGN_MESSAGE,
+			#[cfg(feature = "solana")]
+			Self::SignTransaction(_) | Self::SignTransactions(_) => SOLANA_SIGN_TRANSACTION,
+			#[cfg(feature = "solana")]
+			Self::SignAndSendTransaction(_) | Self::SignAndSendTransactions(_) => {
+				SOLANA_SIGN_AND_SEND_TRANSACTION
+			}
+			#[cfg(feature = "solana")]
+			Self::SignIn(_) | Self::SignInMany(_) => SOLANA_SIGN_IN,
+		}
+	}
+
+	/// The number of items in the request. Single requests have one item and
+	/// `disconnect` has none.
+	pub fn len(&self) -> usize {
+		match self {
+			Self::Disconnect => 0,
+			Self::EncryptMany(props) => props.len(),
+			Self::DecryptMany(props) => props.len(),
+			#[cfg(feature = "solana")]
+			Self::SignMessages(messages) => messages.len(),
+			#[cfg(feature = "solana")]
+			Self::SignTransactions(inputs) => inputs.len(),
+			#[cfg(feature = "solana")]
+			Self::SignAndSendTransactions(inputs) => inputs.len(),
+			#[cfg(feature = "solana")]
+			Self::SignInMany(inputs) => inputs.len(),
+			_ => 1,
+		}
+	}
+
+	/// Whether the request has no items.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Whether the request is a batch, e.g. `sign_transactions`.
+	pub const fn is_batch(&self) -> bool {
+		match self {
+			Self::EncryptMany(_) | Self::DecryptMany(_) => true,
+			#[cfg(feature = "solana")]
+			Self::SignMessages(_)
+			| Self::SignTransactions(_)
+			| Self::SignAndSendTransactions(_)
+			| Self::SignInMany(_) => true,
+			_ => false,
+		}
+	}
+
+	/// Whether the wallet asks the user to approve the request, e.g. in a
+	/// popup. Silent connects, disconnects, encryption and decryption don't
+	/// prompt the user.
+	pub const fn prompts_user(&self) -> bool {
+		match self {
+			Self::Connect(input) => !matches!(input.silent, Some(true)),
+			Self::Disconnect
+			| Self::Encrypt(_)
+			| Self::EncryptMany(_)
+			| Self::Decrypt(_)
+			| Self::DecryptMany(_) => false,
+			#[cfg(feature = "solana")]
+			_ => true,
+		}
+	}
+
+	/// Whether running the request more than once has the same effect as
+	/// running it once. Sending transactions is the only request which isn't
+	/// idempotent, since a retry could send a transaction twice.
+	pub const fn is_idempotent(&self) -> bool {
+		match self {
+			#[cfg(feature = "solana")]
+			Self::SignAndSendTransaction(_) | Self::SignAndSendTransactions(_) => false,
+			_ => true,
+		}
+	}
+}
+
+impl fmt::Display for WalletRequest {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_batch() {
+			write!(f, "{} ({} items)", self.method(), self.len())
+		} else {
+			f.write_str(self.method())
+		}
+	}
+}
@@ -0,0 +1,129 @@
+use std::rc::Rc;
+#[cfg(feature = "timeout")]
+use std::time::Duration;
+
+use async_trait::async_trait;
+#[cfg(feature = "timeout")]
+use futures_timer::Delay;
+
+use super::Next;
+use super::WalletLayer;
+use super::WalletRequest;
+use crate::WalletError;
+use crate::WalletResult;
+
+/// Retries failed requests which are
+/// [idempotent](WalletRequest::is_idempotent).
+///
+/// By default only transient errors are retried, like a wallet which isn't
+/// ready yet or a dropped connection. Errors which need the user, like a
+/// rejected request, a closed or blocked window or a timed out prompt, are
+/// never retried by default. Requests which send transactions are never
+/// retried, and requests which [prompt the user](WalletRequest::prompts_user)
+/// are only retried after [`RetryLayer::retry_prompts`], so a failed popup
+/// isn't reopened behind the back of the user.
+#[derive(Clone)]
+pub struct RetryLayer {
+	max_attempts: usize,
+	retry_prompts: bool,
+	#[cfg(feature = "timeout")]
+	backoff: Option<Duration>,
+	retry_if: Rc<dyn Fn(&WalletError) -> bool>,
+}
+
+impl RetryLayer {
+	/// Make at most `max_attempts` attempts for each request.
+	pub fn new(max_attempts: usize) -> Self {
+		Self {
+			max_attempts: max_attempts.max(1),
+			retry_prompts: false,
+			#[cfg(feature = "timeout")]
+			backoff: None,
+			retry_if: Rc::new(is_retryable),
+		}
+	}
+
+	/// Only retry the errors which match the predicate.
+	#[must_use]
+	pub fn retry_if(mut self, retry_if: impl Fn(&WalletError) -> bool + 'static) -> Self {
+		self.retry_if = Rc::new(retry_if);
+		self
+	}
+
+	/// Also retry requests which prompt the user, like `connect` and
+	/// `sign_message`.
+	#[must_use]
+	pub fn retry_prompts(mut self, retry_prompts: bool) -> Self {
+		self.retry_prompts = retry_prompts;
+		self
+	}
+
+	/// Wait for `backoff` before the first retry, doubling the wait before
+	/// each further retry.
+	#[cfg(feature = "timeout")]
+	#[must_use]
+	pub fn backoff(mut self, backoff: Duration) -> Self {
+		self.backoff = Some(backoff);
+		self
+	}
+
+	pub fn max_attempts(&self) -> usize {
+		self.max_attempts
+	}
+
+	fn retries(&self, request: &WalletRequest) -> bool {
+		request.is_idempotent() && (self.retry_prompts || !request.prompts_user())
+	}
+}
+
+impl std::fmt::Debug for RetryLayer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RetryLayer")
+			.field("max_attempts", &self.max_attempts)
+			.field("retry_prompts", &self.retry_prompts)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Whether the error is transient, so a new attempt could succeed where the
+/// previous attempt failed. Errors thrown by JS wallets are checked by their
+/// [`JsError::kind`](crate::JsError::kind).
+fn is_retryable(error: &WalletError) -> bool {
+	match error {
+		WalletError::Disconnected
+		| WalletError::ChainDisconnected
+		| WalletError::WalletConnection
+		| WalletError::WalletLoad
+		| WalletError::WalletNotReady => true,
+		WalletError::Js(error) => error.kind().as_ref().is_some_and(is_retryable),
+		_ => false,
+	}
+}
+
+#[async_trait(?Send)]
+impl WalletLayer for RetryLayer {
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+		if !self.retries(&request) {
+			return next.run(request).await;
+		}
+
+		let mut attempt = 1;
+		#[cfg(feature = "timeout")]
+		let mut backoff = self.backoff;
+
+		loop {
+			match next.run(request.clone()).await {
+				Err(error) if attempt < self.max_attempts && (self.retry_if)(&error) => {
+					attempt += 1;
+
+					#[cfg(feature = "timeout")]
+					if let Some(wait) = backoff {
+						Delay::new(wait).await;
+						backoff = Some(wait.saturating_mul(2));
+					}
+				}
+				result => return result,
+			}
+		}
+	}
+}
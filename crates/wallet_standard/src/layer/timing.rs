@@ -0,0 +1,112 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::Next;
+use super::WalletLayer;
+use super::WalletRequest;
+use crate::WalletResult;
+
+/// The duration of a request made through a [`TimingLayer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalletTiming {
+	/// The method of the request, e.g. `sign_transaction`.
+	pub method: &'static str,
+	/// The feature which handled the request, e.g. `solana:signTransaction`.
+	pub feature: &'static str,
+	/// The number of items in the request.
+	pub items: usize,
+	/// How long the request took, including every inner layer.
+	pub duration: Duration,
+	/// The [`crate::WalletError::code`] of the failure, or `None` when the
+	/// request succeeded.
+	pub error_code: Option<i32>,
+}
+
+/// A monotonic clock which returns the time elapsed since an arbitrary point.
+pub type WalletClock = Rc<dyn Fn() -> Duration>;
+
+/// Reports the duration of every request, e.g. to record metrics.
+#[derive(Clone)]
+pub struct TimingLayer {
+	report: Rc<dyn Fn(&WalletTiming)>,
+	clock: WalletClock,
+}
+
+impl TimingLayer {
+	/// Call `report` once each request has completed.
+	pub fn new(report: impl Fn(&WalletTiming) + 'static) -> Self {
+		Self {
+			report: Rc::new(report),
+			clock: Rc::new(default_clock),
+		}
+	}
+
+	/// Measure durations with the provided clock instead of the system clock.
+	#[must_use]
+	pub fn clock(mut self, clock: impl Fn() -> Duration + 'static) -> Self {
+		self.clock = Rc::new(clock);
+		self
+	}
+}
+
+impl std::fmt::Debug for TimingLayer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("TimingLayer").finish_non_exhaustive()
+	}
+}
+
+/// `std::time::Instant` panics on `wasm32-unknown-unknown`, so browsers use
+/// `performance.now()`.
+#[cfg(all(feature = "browser", target_arch = "wasm32"))]
+fn default_clock() -> Duration {
+	let performance = js_sys::Reflect::get(
+		&js_sys::global(),
+		&wasm_bindgen::JsValue::from_str("performance"),
+	)
+	.ok()
+	.filter(|performance| !performance.is_undefined());
+	let now = performance
+		.and_then(|performance| {
+			let now =
+				js_sys::Reflect::get(&performance, &wasm_bindgen::JsValue::from_str("now")).ok()?;
+			let now: js_sys::Function = wasm_bindgen::JsCast::dyn_into(now).ok()?;
+			now.call0(&performance).ok()?.as_f64()
+		})
+		.unwrap_or_else(js_sys::Date::now);
+
+	Duration::from_secs_f64(now / 1000.0)
+}
+
+#[cfg(not(all(feature = "browser", target_arch = "wasm32")))]
+fn default_clock() -> Duration {
+	use std::sync::OnceLock;
+	use std::time::Instant;
+
+	static START: OnceLock<Instant> = OnceLock::new();
+
+	START.get_or_init(Instant::now).elapsed()
+}
+
+#[async_trait(?Send)]
+impl WalletLayer for TimingLayer {
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+		let method = request.method();
+		let feature = request.feature();
+		let items = request.len();
+		let start = (self.clock)();
+
+		let result = next.run(request).await;
+
+		(self.report)(&WalletTiming {
+			method,
+			feature,
+			items,
+			duration: (self.clock)().saturating_sub(start),
+			error_code: result.as_ref().err().map(crate::WalletError::code),
+		});
+
+		result
+	}
+}
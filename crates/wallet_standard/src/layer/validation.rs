@@ -0,0 +1,65 @@
+use std::rc::Rc;
+
+use async_trait::async_trait;
+
+use super::Next;
+use super::WalletLayer;
+use super::WalletRequest;
+use crate::WalletError;
+use crate::WalletResult;
+
+type Validator = Rc<dyn Fn(&WalletRequest) -> WalletResult<()>>;
+
+/// Rejects invalid requests before they reach the wallet.
+///
+/// Empty batches always fail with [`WalletError::InvalidArguments`].
+/// Additional rules, like limits or confirmation prompts, can be added with
+/// [`ValidationLayer::rule`].
+#[derive(Clone, Default)]
+pub struct ValidationLayer {
+	rules: Vec<Validator>,
+}
+
+impl ValidationLayer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add a rule which every request must pass. Rules run in the order they
+	/// were added.
+	#[must_use]
+	pub fn rule(mut self, rule: impl Fn(&WalletRequest) -> WalletResult<()> + 'static) -> Self {
+		self.rules.push(Rc::new(rule));
+		self
+	}
+
+	/// Check the request against every rule.
+	///
+	/// # Errors
+	///
+	/// Returns the error of the first rule which fails.
+	pub fn validate(&self, request: &WalletRequest) -> WalletResult<()> {
+		if request.is_batch() && request.is_empty() {
+			return Err(WalletError::InvalidArguments);
+		}
+
+		self.rules.iter().try_for_each(|rule| rule(request))
+	}
+}
+
+impl std::fmt::Debug for ValidationLayer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ValidationLayer")
+			.field("rules", &self.rules.len())
+			.finish()
+	}
+}
+
+#[async_trait(?Send)]
+impl WalletLayer for ValidationLayer {
+	async fn call<T>(&self, request: WalletRequest, next: Next<'_, T>) -> WalletResult<T> {
+		self.validate(&request)?;
+
+		next.run(request).await
+	}
+}
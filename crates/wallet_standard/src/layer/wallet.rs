@@ -0,0 +1,333 @@
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use futures::lock::MutexGuard;
+
+use super::Next;
+use super::WalletLayer;
+use super::WalletLayerStack;
+use super::WalletRequest;
+use crate::ExperimentalDecryptProps;
+use crate::ExperimentalEncryptProps;
+use crate::StandardConnectInput;
+use crate::Wallet;
+use crate::WalletAccounts;
+use crate::WalletError;
+use crate::WalletExperimentalDecrypt;
+use crate::WalletExperimentalEncrypt;
+use crate::WalletResult;
+use crate::WalletStandard;
+use crate::WalletStandardConnect;
+use crate::WalletStandardDisconnect;
+
+/// Run the `request` through the layer and then the wallet.
+///
+/// `next` unpacks the request with the provided pattern and makes the call.
+/// A layer which replaces the request with a different variant fails with
+/// [`WalletError::InvalidArguments`].
+macro_rules! layered {
+	($layer:expr, $request:expr, $pattern:pat => $call:expr) => {{
+		let next = |request: WalletRequest| -> futures::future::LocalBoxFuture<'_, _> {
+			Box::pin(async move {
+				let $pattern = request else {
+					return Err(WalletError::InvalidArguments);
+				};
+
+				$call.await
+			})
+		};
+
+		$layer.call($request, Next::new(&next)).await
+	}};
+}
+
+pub(super) use layered;
+
+/// A wallet which runs every feature call through a [`WalletLayer`].
+///
+/// Layers added with [`LayeredWallet::with`] handle requests before the
+/// layers which were added earlier.
+#[derive(Clone, Debug)]
+pub struct LayeredWallet<W, L> {
+	pub(super) wallet: W,
+	pub(super) layer: L,
+}
+
+impl<W, L: WalletLayer> LayeredWallet<W, L> {
+	pub fn new(wallet: W, layer: L) -> Self {
+		Self { wallet, layer }
+	}
+
+	/// Add a layer which handles requests before the existing layers.
+	pub fn with<O: WalletLayer>(self, layer: O) -> LayeredWallet<W, WalletLayerStack<L, O>> {
+		LayeredWallet {
+			wallet: self.wallet,
+			layer: WalletLayerStack::new(self.layer, layer),
+		}
+	}
+
+	pub fn layer(&self) -> &L {
+		&self.layer
+	}
+
+	/// The wrapped wallet.
+	pub fn inner(&self) -> &W {
+		&self.wallet
+	}
+
+	pub fn into_inner(self) -> W {
+		self.wallet
+	}
+}
+
+impl<W: Wallet, L> Wallet for LayeredWallet<W, L> {
+	type Account = W::Account;
+	type Wallet = W::Wallet;
+
+	fn wallet(&self) -> Self::Wallet {
+		self.wallet.wallet()
+	}
+
+	fn wallet_account(&self) -> Option<Self::Account> {
+		self.wallet.wallet_account()
+	}
+}
+
+impl<W: WalletAccounts, L> WalletAccounts for LayeredWallet<W, L> {
+	fn wallet_accounts(&self) -> Vec<Self::Account> {
+		self.wallet.wallet_accounts()
+	}
+
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account> {
+		self.wallet.select_account(address)
+	}
+
+	fn sync_wallet_account(&mut self) -> Option<Self::Account> {
+		self.wallet.sync_wallet_account()
+	}
+}
+
+/// Borrow the wallet mutably for a single run of `next`.
+fn lock<'a, 'w, W>(wallet: &'a Mutex<&'w mut W>) -> WalletResult<MutexGuard<'a, &'w mut W>> {
+	wallet.try_lock().ok_or(WalletError::InvalidArguments)
+}
+
+#[async_trait(?Send)]
+impl<W: WalletStandard, L: WalletLayer> WalletStandardConnect for LayeredWallet<W, L> {
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>> {
+		self.connect_with_options(StandardConnectInput::default())
+			.await
+	}
+
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>> {
+		let wallet = &Mutex::new(&mut self.wallet);
+
+		layered!(
+			self.layer,
+			WalletRequest::Connect(options),
+			WalletRequest::Connect(options) => lock(wallet)?.connect_with_options(options)
+		)
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletStandard, L: WalletLayer> WalletStandardDisconnect for LayeredWallet<W, L> {
+	async fn disconnect(&mut self) -> WalletResult<()> {
+		let wallet = &Mutex::new(&mut self.wallet);
+
+		layered!(
+			self.layer,
+			WalletRequest::Disconnect,
+			WalletRequest::Disconnect => lock(wallet)?.disconnect()
+		)
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletExperimentalEncrypt, L: WalletLayer> WalletExperimentalEncrypt
+	for LayeredWallet<W, L>
+{
+	type Output = W::Output;
+
+	async fn encrypt_many(
+		&self,
+		props: Vec<ExperimentalEncryptProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		layered!(
+			self.layer,
+			WalletRequest::EncryptMany(props),
+			WalletRequest::EncryptMany(props) => self.wallet.encrypt_many(props)
+		)
+	}
+
+	async fn encrypt(&self, props: ExperimentalEncryptProps) -> WalletResult<Self::Output> {
+		layered!(
+			self.layer,
+			WalletRequest::Encrypt(props),
+			WalletRequest::Encrypt(props) => self.wallet.encrypt(props)
+		)
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletExperimentalDecrypt, L: WalletLayer> WalletExperimentalDecrypt
+	for LayeredWallet<W, L>
+{
+	type Output = W::Output;
+
+	async fn decrypt_many(
+		&self,
+		props: Vec<ExperimentalDecryptProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		layered!(
+			self.layer,
+			WalletRequest::DecryptMany(props),
+			WalletRequest::DecryptMany(props) => self.wallet.decrypt_many(props)
+		)
+	}
+
+	async fn decrypt(&self, props: ExperimentalDecryptProps) -> WalletResult<Self::Output> {
+		layered!(
+			self.layer,
+			WalletRequest::Decrypt(props),
+			WalletRequest::Decrypt(props) => self.wallet.decrypt(props)
+		)
+	}
+}
+
+#[cfg(feature = "solana")]
+mod solana {
+	use async_trait::async_trait;
+
+	use super::super::Next;
+	use super::super::WalletLayer;
+	use super::super::WalletRequest;
+	use super::LayeredWallet;
+	use super::layered;
+	use crate::SolanaSignAndSendTransactionProps;
+	use crate::SolanaSignInInput;
+	use crate::SolanaSignTransactionProps;
+	use crate::WalletError;
+	use crate::WalletResult;
+	use crate::WalletSolanaSignAndSendTransaction;
+	use crate::WalletSolanaSignIn;
+	use crate::WalletSolanaSignMessage;
+	use crate::WalletSolanaSignTransaction;
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignMessage, L: WalletLayer> WalletSolanaSignMessage for LayeredWallet<W, L> {
+		type Output = W::Output;
+
+		async fn sign_message_async(
+			&self,
+			message: impl Into<Vec<u8>>,
+		) -> WalletResult<Self::Output> {
+			layered!(
+				self.layer,
+				WalletRequest::SignMessage(message.into()),
+				WalletRequest::SignMessage(message) => self.wallet.sign_message_async(message)
+			)
+		}
+
+		async fn sign_messages<M: Into<Vec<u8>>>(
+			&self,
+			messages: Vec<M>,
+		) -> WalletResult<Vec<Self::Output>> {
+			let messages = messages.into_iter().map(Into::into).collect();
+
+			layered!(
+				self.layer,
+				WalletRequest::SignMessages(messages),
+				WalletRequest::SignMessages(messages) => self.wallet.sign_messages(messages)
+			)
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignTransaction, L: WalletLayer> WalletSolanaSignTransaction
+		for LayeredWallet<W, L>
+	{
+		type Output = W::Output;
+
+		async fn sign_transaction(
+			&self,
+			props: SolanaSignTransactionProps,
+		) -> WalletResult<Self::Output> {
+			layered!(
+				self.layer,
+				WalletRequest::SignTransaction(props),
+				WalletRequest::SignTransaction(props) => self.wallet.sign_transaction(props)
+			)
+		}
+
+		async fn sign_transactions(
+			&self,
+			inputs: Vec<SolanaSignTransactionProps>,
+		) -> WalletResult<Vec<Self::Output>> {
+			layered!(
+				self.layer,
+				WalletRequest::SignTransactions(inputs),
+				WalletRequest::SignTransactions(inputs) => self.wallet.sign_transactions(inputs)
+			)
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignAndSendTransaction, L: WalletLayer> WalletSolanaSignAndSendTransaction
+		for LayeredWallet<W, L>
+	{
+		type Output = W::Output;
+
+		async fn sign_and_send_transaction(
+			&self,
+			props: SolanaSignAndSendTransactionProps,
+		) -> WalletResult<Self::Output> {
+			layered!(
+				self.layer,
+				WalletRequest::SignAndSendTransaction(props),
+				WalletRequest::SignAndSendTransaction(props) => {
+					self.wallet.sign_and_send_transaction(props)
+				}
+			)
+		}
+
+		async fn sign_and_send_transactions(
+			&self,
+			inputs: Vec<SolanaSignAndSendTransactionProps>,
+		) -> WalletResult<Vec<Self::Output>> {
+			layered!(
+				self.layer,
+				WalletRequest::SignAndSendTransactions(inputs),
+				WalletRequest::SignAndSendTransactions(inputs) => {
+					self.wallet.sign_and_send_transactions(inputs)
+				}
+			)
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignIn, L: WalletLayer> WalletSolanaSignIn for LayeredWallet<W, L> {
+		type Output = W::Output;
+
+		async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
+			layered!(
+				self.layer,
+				WalletRequest::SignIn(input),
+				WalletRequest::SignIn(input) => self.wallet.sign_in(input)
+			)
+		}
+
+		async fn sign_in_many(
+			&self,
+			inputs: Vec<SolanaSignInInput>,
+		) -> WalletResult<Vec<Self::Output>> {
+			layered!(
+				self.layer,
+				WalletRequest::SignInMany(inputs),
+				WalletRequest::SignInMany(inputs) => self.wallet.sign_in_many(inputs)
+			)
+		}
+	}
+}
@@ -1,18 +1,60 @@
-#![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"))]
+#![cfg_attr(
+	not(doctest),
+	doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"))
+)]
 
+pub use accounts::*;
+pub use batch::*;
+pub use caip::*;
+pub use connection::*;
 pub use error::*;
 pub use experimental::*;
+pub use icon::*;
+pub use identifier::*;
+pub use layer::*;
+pub use snapshot::*;
 #[cfg(feature = "solana")]
 pub use solana::*;
 pub use standard::*;
+#[cfg(feature = "svg")]
+pub use svg::*;
+#[cfg(feature = "timeout")]
+pub use timeout::*;
 pub use types::*;
+pub use wallet_event::*;
 
+mod accounts;
+mod batch;
+mod caip;
+#[cfg(feature = "solana")]
+pub mod conformance;
+mod connection;
 mod error;
 mod experimental;
+mod icon;
+mod identifier;
+mod layer;
+pub mod send;
+#[cfg(feature = "siws")]
+pub mod siws;
+mod snapshot;
 #[cfg(feature = "solana")]
 mod solana;
 mod standard;
+#[cfg(feature = "svg")]
+mod svg;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "timeout")]
+mod timeout;
 mod types;
+mod wallet_event;
+
+/// The readme examples use the Solana features, so they are only tested with
+/// the `solana` feature enabled.
+#[cfg(all(doctest, feature = "solana"))]
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"))]
+struct ReadmeDoctests;
 
 pub mod prelude {
 	pub use super::ExperimentalDecryptOutput;
@@ -21,6 +63,7 @@ pub mod prelude {
 	pub use super::StandardConnectOutput;
 	pub use super::Wallet;
 	pub use super::WalletAccountInfo;
+	pub use super::WalletAccounts;
 	pub use super::WalletError;
 	pub use super::WalletExperimentalDecrypt;
 	pub use super::WalletExperimentalEncrypt;
@@ -0,0 +1,224 @@
+//! Wallet traits which produce `Send` futures.
+//!
+//! The traits at the crate root are declared with `#[async_trait(?Send)]`
+//! since browser wallets are backed by JS values which can't leave their
+//! thread. Native wallets don't have this restriction, but their futures can't
+//! be used from multi-threaded runtimes (e.g. in an `axum` handler) through the
+//! `?Send` traits.
+//!
+//! This module mirrors every wallet trait with a `Send` version. Each trait
+//! has a blanket implementation of its `?Send` counterpart, so a wallet which
+//! implements the traits of this module can still be used with code which is
+//! generic over the crate root traits, like [`crate::conformance`]. Browser
+//! wallets should keep implementing the `?Send` traits.
+//!
+//! ```rust
+//! # #[cfg(feature = "solana")]
+//! # {
+//! use solana_keypair::Keypair;
+//! use wallet_standard::KeypairWallet;
+//! use wallet_standard::send::WalletSolanaSignMessage;
+//! use wallet_standard::send::WalletStandardConnect;
+//!
+//! let mut wallet = KeypairWallet::new(Keypair::new());
+//!
+//! std::thread::spawn(move || {
+//! 	futures::executor::block_on(async move {
+//! 		wallet.connect().await?;
+//! 		wallet.sign_message_async("hello").await
+//! 	})
+//! })
+//! .join()
+//! .unwrap()
+//! .unwrap();
+//! # }
+//! ```
+
+use async_trait::async_trait;
+
+use crate::BatchResult;
+use crate::ExperimentalDecryptOutput;
+use crate::ExperimentalDecryptProps;
+use crate::ExperimentalEncryptOutput;
+use crate::ExperimentalEncryptProps;
+use crate::StandardConnectInput;
+use crate::Wallet;
+use crate::WalletResult;
+#[cfg(feature = "solana")]
+pub use crate::solana::send::*;
+use crate::try_batch;
+
+/// The `Send` version of [`crate::WalletStandardConnect`].
+#[async_trait]
+pub trait WalletStandardConnect: Wallet<Account: Send> + Send + Sync {
+	/// Connect to the wallet and return the authorized accounts.
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>>;
+	/// Connect to the wallet with the provided options.
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>>;
+}
+
+#[async_trait(?Send)]
+impl<T: WalletStandardConnect> crate::WalletStandardConnect for T {
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>> {
+		WalletStandardConnect::connect(self).await
+	}
+
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>> {
+		WalletStandardConnect::connect_with_options(self, options).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletStandardDisconnect`].
+#[async_trait]
+pub trait WalletStandardDisconnect: Wallet + Send + Sync {
+	/// Disconnect from the wallet.
+	async fn disconnect(&mut self) -> WalletResult<()>;
+}
+
+#[async_trait(?Send)]
+impl<T: WalletStandardDisconnect> crate::WalletStandardDisconnect for T {
+	async fn disconnect(&mut self) -> WalletResult<()> {
+		WalletStandardDisconnect::disconnect(self).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletStandard`].
+pub trait WalletStandard: WalletStandardConnect + WalletStandardDisconnect {}
+
+impl<T> WalletStandard for T where T: WalletStandardConnect + WalletStandardDisconnect {}
+
+/// The `Send` version of [`crate::WalletExperimentalEncrypt`].
+#[async_trait]
+pub trait WalletExperimentalEncrypt: Send + Sync {
+	type Output: ExperimentalEncryptOutput + Send;
+
+	async fn encrypt_many(
+		&self,
+		props: Vec<ExperimentalEncryptProps>,
+	) -> WalletResult<Vec<Self::Output>>;
+	async fn encrypt(&self, props: ExperimentalEncryptProps) -> WalletResult<Self::Output>;
+
+	/// Encrypt each of the `props` in order. See
+	/// [`crate::WalletExperimentalEncrypt::encrypt_each`].
+	async fn encrypt_each(
+		&self,
+		props: Vec<ExperimentalEncryptProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(props, |props| self.encrypt(props)).await
+	}
+}
+
+#[async_trait(?Send)]
+impl<T: WalletExperimentalEncrypt> crate::WalletExperimentalEncrypt for T {
+	type Output = <T as WalletExperimentalEncrypt>::Output;
+
+	async fn encrypt_many(
+		&self,
+		props: Vec<ExperimentalEncryptProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		WalletExperimentalEncrypt::encrypt_many(self, props).await
+	}
+
+	async fn encrypt(&self, props: ExperimentalEncryptProps) -> WalletResult<Self::Output> {
+		WalletExperimentalEncrypt::encrypt(self, props).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletExperimentalDecrypt`].
+#[async_trait]
+pub trait WalletExperimentalDecrypt: Send + Sync {
+	type Output: ExperimentalDecryptOutput + Send;
+
+	async fn decrypt_many(
+		&self,
+		props: Vec<ExperimentalDecryptProps>,
+	) -> WalletResult<Vec<Self::Output>>;
+	async fn decrypt(&self, props: ExperimentalDecryptProps) -> WalletResult<Self::Output>;
+
+	/// Decrypt each of the `props` in order. See
+	/// [`crate::WalletExperimentalDecrypt::decrypt_each`].
+	async fn decrypt_each(
+		&self,
+		props: Vec<ExperimentalDecryptProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(props, |props| self.decrypt(props)).await
+	}
+}
+
+#[async_trait(?Send)]
+impl<T: WalletExperimentalDecrypt> crate::WalletExperimentalDecrypt for T {
+	type Output = <T as WalletExperimentalDecrypt>::Output;
+
+	async fn decrypt_many(
+		&self,
+		props: Vec<ExperimentalDecryptProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		WalletExperimentalDecrypt::decrypt_many(self, props).await
+	}
+
+	async fn decrypt(&self, props: ExperimentalDecryptProps) -> WalletResult<Self::Output> {
+		WalletExperimentalDecrypt::decrypt(self, props).await
+	}
+}
+
+#[cfg(feature = "testing")]
+#[cfg(test)]
+mod tests {
+	use std::thread;
+
+	use futures::executor::block_on;
+
+	use super::*;
+	use crate::SignInDomain;
+	use crate::SolanaSignInInput;
+	use crate::SolanaSignMessageOutput;
+	use crate::testing::MockWallet;
+
+	fn assert_send<F: Future + Send>(future: F) -> F {
+		future
+	}
+
+	#[test]
+	fn futures_can_move_between_threads() -> WalletResult<()> {
+		let mut wallet = MockWallet::default();
+		let handle = wallet.handle();
+
+		let output = thread::spawn(move || {
+			block_on(async move {
+				assert_send(wallet.connect()).await?;
+				let input = SolanaSignInInput::builder()
+					.domain(SignInDomain::new("example.com")?)
+					.build();
+				assert_send(wallet.sign_in(input)).await?;
+				assert_send(wallet.sign_message_async(b"hello".to_vec())).await
+			})
+		})
+		.join()
+		.unwrap()?;
+
+		assert_eq!(output.signed_message(), b"hello");
+		assert_eq!(handle.calls().len(), 3);
+
+		Ok(())
+	}
+
+	#[test]
+	fn send_wallets_implement_the_unsend_traits() -> WalletResult<()> {
+		fn connect_unsend<W: crate::WalletStandard>(wallet: &mut W) -> WalletResult<()> {
+			block_on(crate::WalletStandardConnect::connect(wallet)).map(|_| ())
+		}
+
+		let mut wallet = MockWallet::default();
+		connect_unsend(&mut wallet)?;
+
+		assert!(wallet.connected());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,442 @@
+//! Server side [Sign In With Solana](https://siws.web3auth.io/) flows.
+//!
+//! A [`SiwsServer`] issues challenges, which are passed to the `solana:signIn`
+//! feature of a wallet, and verifies the outputs which come back. Each
+//! challenge has a random nonce, which is consumed by the first attempt to
+//! verify it so a signed message can't be replayed.
+//!
+//! ```rust
+//! use solana_keypair::Keypair;
+//! use wallet_standard::KeypairWallet;
+//! use wallet_standard::SignInDomain;
+//! use wallet_standard::prelude::*;
+//! use wallet_standard::siws::InMemoryChallengeStore;
+//! use wallet_standard::siws::SiwsConfig;
+//! use wallet_standard::siws::SiwsServer;
+//!
+//! # futures::executor::block_on(async {
+//! let server = SiwsServer::new(
+//! 	SiwsConfig::builder()
+//! 		.domain(SignInDomain::new("example.com")?)
+//! 		.build(),
+//! 	InMemoryChallengeStore::default(),
+//! );
+//! let mut wallet = KeypairWallet::new(Keypair::new());
+//! wallet.connect().await?;
+//!
+//! let challenge = server.challenge().await?;
+//! let output = wallet.sign_in(challenge).await?;
+//! let session = server.verify(&output).await?;
+//! assert_eq!(session.address, wallet.solana_pubkey().to_string());
+//!
+//! // The nonce has been used.
+//! assert!(server.verify(&output).await.is_err());
+//! # WalletResult::Ok(())
+//! # })
+//! # .unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::Serialize;
+use time::OffsetDateTime;
+use typed_builder::TypedBuilder;
+
+use crate::SignInClock;
+use crate::SignInDomain;
+use crate::SignInTimestamp;
+use crate::SignInUri;
+use crate::SignInVerificationError;
+use crate::SignInVerifier;
+use crate::SignInVersion;
+use crate::SolanaSignInInput;
+use crate::SolanaSignInOutput;
+use crate::WalletError;
+use crate::WalletResult;
+use crate::parse_sign_in_message;
+
+/// The characters used for nonces and request ids.
+const ALPHANUMERIC: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// The length of a nonce, which gives more than 128 bits of randomness.
+const NONCE_LENGTH: usize = 22;
+/// The length of a request id.
+const REQUEST_ID_LENGTH: usize = 16;
+
+/// The fields shared by every challenge issued by a [`SiwsServer`].
+#[derive(Clone, Debug, PartialEq, Eq, TypedBuilder)]
+pub struct SiwsConfig {
+	/// The domain requesting the sign in, e.g. `example.com`.
+	#[builder(setter(into))]
+	pub domain: SignInDomain,
+	/// The URI of the resource which is the subject of the sign in.
+	#[builder(default, setter(into, strip_option))]
+	pub uri: Option<SignInUri>,
+	/// A statement for the user, e.g. the terms of service.
+	#[builder(default, setter(into, strip_option))]
+	pub statement: Option<String>,
+	/// The chain to sign in on, e.g. `solana:mainnet`.
+	#[builder(default, setter(into, strip_option))]
+	pub chain_id: Option<String>,
+	/// The resources which the user is asked to authorize.
+	#[builder(default, setter(into, strip_option))]
+	pub resources: Option<Vec<SignInUri>>,
+	/// How long a challenge can be used for. Defaults to five minutes.
+	#[builder(default = Duration::from_secs(300))]
+	pub ttl: Duration,
+	/// The allowed difference between the clocks of the wallet and the
+	/// server.
+	#[builder(default)]
+	pub clock_skew: Duration,
+}
+
+/// A challenge issued by a [`SiwsServer`] which hasn't been used yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiwsChallenge {
+	/// The nonce of the challenge, which identifies it in the store.
+	pub nonce: String,
+	/// The input passed to the wallet.
+	pub input: SolanaSignInInput,
+	/// When the challenge expires and can be removed from the store.
+	pub expires_at: OffsetDateTime,
+}
+
+/// Persists the challenges issued by a [`SiwsServer`] until they're used,
+/// e.g. in a database shared by several servers.
+#[async_trait]
+pub trait ChallengeStore: Send + Sync {
+	/// Save the `challenge` under its nonce.
+	async fn insert(&self, challenge: SiwsChallenge) -> WalletResult<()>;
+
+	/// Remove and return the challenge with the `nonce`. Every challenge can
+	/// be taken at most once.
+	async fn take(&self, nonce: &str) -> WalletResult<Option<SiwsChallenge>>;
+
+	/// Remove the challenges which expired before `now`. Stores which expire
+	/// entries themselves can keep the default, which does nothing.
+	async fn remove_expired(&self, now: OffsetDateTime) -> WalletResult<()> {
+		let _ = now;
+		Ok(())
+	}
+}
+
+/// A [`ChallengeStore`] which keeps challenges in memory. Clones share the
+/// same challenges.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryChallengeStore {
+	challenges: Arc<Mutex<HashMap<String, SiwsChallenge>>>,
+}
+
+impl InMemoryChallengeStore {
+	/// The number of challenges waiting to be used.
+	pub fn len(&self) -> usize {
+		self.lock().len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, SiwsChallenge>> {
+		self.challenges
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+	}
+}
+
+#[async_trait]
+impl ChallengeStore for InMemoryChallengeStore {
+	async fn insert(&self, challenge: SiwsChallenge) -> WalletResult<()> {
+		self.lock().insert(challenge.nonce.clone(), challenge);
+		Ok(())
+	}
+
+	async fn take(&self, nonce: &str) -> WalletResult<Option<SiwsChallenge>> {
+		Ok(self.lock().remove(nonce))
+	}
+
+	async fn remove_expired(&self, now: OffsetDateTime) -> WalletResult<()> {
+		self.lock()
+			.retain(|_, challenge| challenge.expires_at > now);
+		Ok(())
+	}
+}
+
+/// An account which signed in with a [`SiwsServer`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiwsSession {
+	/// The address of the account.
+	pub address: String,
+	/// The chain which the account signed in on.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub chain_id: Option<String>,
+	/// The domain which the account signed in to.
+	pub domain: SignInDomain,
+	/// The request id of the challenge.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub request_id: Option<String>,
+	/// When the signed message expires.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub expiration_time: Option<SignInTimestamp>,
+}
+
+/// Issues sign in challenges and verifies the signed outputs.
+#[derive(Clone)]
+pub struct SiwsServer<S> {
+	config: SiwsConfig,
+	store: S,
+	clock: SignInClock,
+}
+
+impl<S: ChallengeStore> SiwsServer<S> {
+	pub fn new(config: SiwsConfig, store: S) -> Self {
+		Self {
+			config,
+			store,
+			clock: Arc::new(OffsetDateTime::now_utc),
+		}
+	}
+
+	/// Issue and check challenges with the provided clock instead of the
+	/// system clock.
+	#[must_use]
+	pub fn clock(mut self, clock: impl Fn() -> OffsetDateTime + Send + Sync + 'static) -> Self {
+		self.clock = Arc::new(clock);
+		self
+	}
+
+	pub fn config(&self) -> &SiwsConfig {
+		&self.config
+	}
+
+	pub fn store(&self) -> &S {
+		&self.store
+	}
+
+	/// Issue a challenge to pass to `solana:signIn`. The address is left for
+	/// the wallet to choose.
+	pub async fn challenge(&self) -> WalletResult<SolanaSignInInput> {
+		let now = (self.clock)();
+		self.store.remove_expired(now).await?;
+
+		let issued_at = now.replace_nanosecond(0).unwrap_or(now);
+		let expires_at = issued_at + self.config.ttl;
+		let expiration_time = SignInTimestamp::from_time(expires_at)?;
+		let nonce = random_string(NONCE_LENGTH)?;
+		let input = SolanaSignInInput {
+			domain: Some(self.config.domain.to_string()),
+			address: None,
+			statement: self.config.statement.clone(),
+			uri: self.config.uri.as_ref().map(ToString::to_string),
+			version: Some(SignInVersion::V1.into()),
+			chain_id: self.config.chain_id.clone(),
+			nonce: Some(nonce.clone()),
+			issued_at: Some(SignInTimestamp::from_time(issued_at)?.into()),
+			expiration_time: Some(expiration_time.into()),
+			not_before: None,
+			request_id: Some(random_string(REQUEST_ID_LENGTH)?),
+			resources: self
+				.config
+				.resources
+				.as_ref()
+				.map(|resources| resources.iter().map(ToString::to_string).collect()),
+		};
+
+		self.store
+			.insert(SiwsChallenge {
+				nonce,
+				input: input.clone(),
+				expires_at,
+			})
+			.await?;
+
+		Ok(input)
+	}
+
+	/// Verify the `output` of signing in with a challenge from this server.
+	///
+	/// The challenge is consumed before the output is checked, so it can't be
+	/// used again even when verification fails.
+	pub async fn verify(
+		&self,
+		output: &(impl SolanaSignInOutput + Sync),
+	) -> WalletResult<SiwsSession> {
+		let nonce = parse_sign_in_message(&output.signed_message())?.nonce;
+		let challenge = match nonce.as_deref() {
+			Some(nonce) => self.store.take(nonce).await?,
+			None => None,
+		};
+		let Some(challenge) = challenge else {
+			return Err(SignInVerificationError::UnknownNonce(nonce).into());
+		};
+
+		let clock = self.clock.clone();
+		let mut verifier = SignInVerifier::new()
+			.domain(self.config.domain.clone())
+			.clock_skew(self.config.clock_skew)
+			.clock(move || clock());
+
+		if let Some(ref chain_id) = self.config.chain_id {
+			verifier = verifier.chain_ids([chain_id.clone()]);
+		}
+
+		let signed = verifier.verify(&challenge.input, output)?;
+
+		Ok(SiwsSession {
+			domain: signed
+				.try_domain()?
+				.unwrap_or_else(|| self.config.domain.clone()),
+			expiration_time: signed.try_expiration_time()?,
+			address: signed.address.unwrap_or_default(),
+			chain_id: signed.chain_id,
+			request_id: signed.request_id,
+		})
+	}
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for SiwsServer<S> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SiwsServer")
+			.field("config", &self.config)
+			.field("store", &self.store)
+			.finish_non_exhaustive()
+	}
+}
+
+/// A random alphanumeric string, as required for nonces by EIP-4361.
+fn random_string(length: usize) -> WalletResult<String> {
+	let mut value = String::with_capacity(length);
+	let mut bytes = [0u8; 32];
+
+	while value.len() < length {
+		getrandom::getrandom(&mut bytes)
+			.map_err(|error| WalletError::External(error.to_string()))?;
+
+		// Bytes above the largest multiple of 62 are skipped to avoid bias.
+		for byte in bytes.iter().filter(|byte| **byte < 248) {
+			if value.len() == length {
+				break;
+			}
+
+			value.push(char::from(ALPHANUMERIC[usize::from(byte % 62)]));
+		}
+	}
+
+	Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+	use solana_keypair::Keypair;
+	use time::macros::datetime;
+
+	use super::*;
+	use crate::SolanaChain;
+	use crate::WalletSolanaPubkey;
+	use crate::WalletSolanaSignIn;
+	use crate::solana::connected_wallet;
+
+	#[test]
+	fn challenges_are_unique() -> WalletResult<()> {
+		let server = SiwsServer::new(
+			SiwsConfig::builder()
+				.domain(SignInDomain::new("example.com")?)
+				.build(),
+			InMemoryChallengeStore::default(),
+		);
+		let first = block_on(server.challenge())?;
+		let second = block_on(server.challenge())?;
+
+		assert_ne!(first.nonce, second.nonce);
+		assert_ne!(first.request_id, second.request_id);
+		assert_eq!(first.nonce.as_ref().map(String::len), Some(NONCE_LENGTH));
+		assert!(
+			first
+				.nonce
+				.unwrap()
+				.bytes()
+				.all(|byte| byte.is_ascii_alphanumeric())
+		);
+		assert_eq!(server.store().len(), 2);
+
+		Ok(())
+	}
+
+	#[test]
+	fn verifying_consumes_the_nonce() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let server = SiwsServer::new(
+			SiwsConfig::builder()
+				.domain(SignInDomain::new("example.com")?)
+				.uri(SignInUri::new("https://example.com/login")?)
+				.chain_id(SolanaChain::Devnet)
+				.build(),
+			InMemoryChallengeStore::default(),
+		);
+		let challenge = block_on(server.challenge())?;
+		let output = block_on(wallet.sign_in(challenge.clone()))?;
+		let session = block_on(server.verify(&output))?;
+
+		assert_eq!(session.address, wallet.solana_pubkey().to_string());
+		assert_eq!(
+			session.chain_id.as_deref(),
+			Some(SolanaChain::Devnet.as_str())
+		);
+		assert_eq!(session.domain.as_str(), "example.com");
+		assert_eq!(session.request_id, challenge.request_id);
+		assert_eq!(
+			block_on(server.verify(&output)),
+			Err(SignInVerificationError::UnknownNonce(challenge.nonce).into())
+		);
+		assert!(server.store().is_empty());
+
+		Ok(())
+	}
+
+	#[test]
+	fn expired_challenges_fail_and_are_removed() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let now = Arc::new(Mutex::new(datetime!(2024-01-01 00:00 UTC)));
+		let server = SiwsServer::new(
+			SiwsConfig::builder()
+				.domain(SignInDomain::new("example.com")?)
+				.ttl(Duration::from_secs(60))
+				.build(),
+			InMemoryChallengeStore::default(),
+		)
+		.clock({
+			let now = now.clone();
+			move || *now.lock().unwrap()
+		});
+
+		let challenge = block_on(server.challenge())?;
+		assert_eq!(challenge.issued_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+		assert_eq!(
+			challenge.expiration_time.as_deref(),
+			Some("2024-01-01T00:01:00Z")
+		);
+		let output = block_on(wallet.sign_in(challenge))?;
+
+		*now.lock().unwrap() = datetime!(2024-01-01 00:02 UTC);
+		assert_eq!(
+			block_on(server.verify(&output)),
+			Err(
+				SignInVerificationError::Expired(SignInTimestamp::new("2024-01-01T00:01:00Z")?)
+					.into()
+			)
+		);
+
+		block_on(server.challenge())?;
+		*now.lock().unwrap() = datetime!(2024-01-01 00:04 UTC);
+		block_on(server.challenge())?;
+		assert_eq!(server.store().len(), 1);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,224 @@
+use serde::Deserialize;
+use serde::Serialize;
+use typed_builder::TypedBuilder;
+
+use crate::WalletAccountInfo;
+use crate::WalletInfo;
+
+/// An owned copy of a [`WalletAccountInfo`].
+///
+/// Unlike live handles, such as the JS backed accounts of the browser crate,
+/// snapshots can be stored, serialized, sent across threads and rendered on
+/// the server.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletAccountSnapshot {
+	#[builder(setter(into))]
+	pub address: String,
+	#[builder(setter(into))]
+	pub public_key: Vec<u8>,
+	#[builder(default)]
+	pub chains: Vec<String>,
+	#[builder(default)]
+	pub features: Vec<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub label: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub icon: Option<String>,
+}
+
+impl WalletAccountSnapshot {
+	/// Copy every property of the account.
+	pub fn from_account(account: &impl WalletAccountInfo) -> Self {
+		Self {
+			address: account.address(),
+			public_key: account.public_key(),
+			chains: account.chains(),
+			features: account.features(),
+			label: account.label(),
+			icon: account.icon(),
+		}
+	}
+}
+
+impl<A: WalletAccountInfo> From<&A> for WalletAccountSnapshot {
+	fn from(account: &A) -> Self {
+		Self::from_account(account)
+	}
+}
+
+impl WalletAccountInfo for WalletAccountSnapshot {
+	fn address(&self) -> String {
+		self.address.clone()
+	}
+
+	fn public_key(&self) -> Vec<u8> {
+		self.public_key.clone()
+	}
+
+	fn chains(&self) -> Vec<String> {
+		self.chains.clone()
+	}
+
+	fn features(&self) -> Vec<String> {
+		self.features.clone()
+	}
+
+	fn label(&self) -> Option<String> {
+		self.label.clone()
+	}
+
+	fn icon(&self) -> Option<String> {
+		self.icon.clone()
+	}
+}
+
+/// An owned copy of a [`WalletInfo`] and all of its accounts.
+///
+/// ```
+/// use wallet_standard::WalletAccountSnapshot;
+/// use wallet_standard::WalletInfo;
+/// use wallet_standard::WalletInfoSnapshot;
+///
+/// let snapshot = WalletInfoSnapshot::builder()
+/// 	.version("1.0.0")
+/// 	.name("Example")
+/// 	.icon("data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=")
+/// 	.accounts(vec![
+/// 		WalletAccountSnapshot::builder()
+/// 			.address("address")
+/// 			.public_key(vec![0; 32])
+/// 			.build(),
+/// 	])
+/// 	.build();
+///
+/// // Snapshots can be created from any `WalletInfo`, including themselves.
+/// assert_eq!(WalletInfoSnapshot::from(&snapshot), snapshot);
+/// assert_eq!(snapshot.accounts().len(), 1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletInfoSnapshot {
+	#[builder(setter(into))]
+	pub version: String,
+	#[builder(setter(into))]
+	pub name: String,
+	#[builder(setter(into))]
+	pub icon: String,
+	#[builder(default)]
+	pub chains: Vec<String>,
+	#[builder(default)]
+	pub features: Vec<String>,
+	#[builder(default)]
+	pub accounts: Vec<WalletAccountSnapshot>,
+}
+
+impl WalletInfoSnapshot {
+	/// Copy every property of the wallet and its accounts.
+	pub fn from_wallet(wallet: &impl WalletInfo) -> Self {
+		Self {
+			version: wallet.version(),
+			name: wallet.name(),
+			icon: wallet.icon(),
+			chains: wallet.chains(),
+			features: wallet.features(),
+			accounts: wallet
+				.accounts()
+				.iter()
+				.map(WalletAccountSnapshot::from_account)
+				.collect(),
+		}
+	}
+}
+
+impl<W: WalletInfo> From<&W> for WalletInfoSnapshot {
+	fn from(wallet: &W) -> Self {
+		Self::from_wallet(wallet)
+	}
+}
+
+impl WalletInfo for WalletInfoSnapshot {
+	type Account = WalletAccountSnapshot;
+
+	fn version(&self) -> String {
+		self.version.clone()
+	}
+
+	fn name(&self) -> String {
+		self.name.clone()
+	}
+
+	fn icon(&self) -> String {
+		self.icon.clone()
+	}
+
+	fn chains(&self) -> Vec<String> {
+		self.chains.clone()
+	}
+
+	fn features(&self) -> Vec<String> {
+		self.features.clone()
+	}
+
+	fn accounts(&self) -> Vec<Self::Account> {
+		self.accounts.clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn snapshots_round_trip_through_serde() -> Result<(), serde_json::Error> {
+		let snapshot = WalletInfoSnapshot::builder()
+			.version("1.0.0")
+			.name("Example")
+			.icon("data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=")
+			.chains(vec!["solana:devnet".into()])
+			.features(vec!["standard:connect".into()])
+			.accounts(vec![
+				WalletAccountSnapshot::builder()
+					.address("address")
+					.public_key(vec![1, 2, 3])
+					.label("Main")
+					.build(),
+			])
+			.build();
+		let json = serde_json::to_value(&snapshot)?;
+
+		assert_eq!(
+			json["accounts"][0]["publicKey"],
+			serde_json::json!([1, 2, 3])
+		);
+		assert_eq!(
+			serde_json::from_value::<WalletInfoSnapshot>(json)?,
+			snapshot
+		);
+
+		Ok(())
+	}
+
+	#[cfg(feature = "solana")]
+	#[test]
+	fn snapshot_of_keypair_wallet() {
+		use solana_keypair::Keypair;
+		use solana_signer::Signer;
+
+		use crate::KeypairWallet;
+		use crate::Wallet;
+
+		let keypair = Keypair::new();
+		let pubkey = keypair.pubkey();
+		let mut wallet = KeypairWallet::new(keypair);
+		futures::executor::block_on(crate::WalletStandardConnect::connect(&mut wallet)).unwrap();
+
+		let snapshot = WalletInfoSnapshot::from(&wallet.wallet());
+
+		assert_eq!(snapshot.name(), wallet.name());
+		assert_eq!(snapshot.chains, wallet.wallet().chains());
+		assert_eq!(snapshot.accounts.len(), 1);
+		assert_eq!(snapshot.accounts[0].address, pubkey.to_string());
+		assert_eq!(snapshot.accounts[0].public_key, pubkey.to_bytes().to_vec());
+	}
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Caip2ChainId;
+use crate::ChainId;
+use crate::SOLANA_DEVNET_GENESIS_HASH;
+use crate::SOLANA_MAINNET_GENESIS_HASH;
+use crate::SOLANA_TESTNET_GENESIS_HASH;
+use crate::WalletError;
+use crate::WalletResult;
+
+pub const SOLANA_MAINNET_CHAIN: &str = "solana:mainnet";
+pub const SOLANA_DEVNET_CHAIN: &str = "solana:devnet";
+pub const SOLANA_TESTNET_CHAIN: &str = "solana:testnet";
+pub const SOLANA_LOCALNET_CHAIN: &str = "solana:localnet";
+
+pub const SOLANA_MAINNET_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+pub const SOLANA_DEVNET_RPC_URL: &str = "https://api.devnet.solana.com";
+pub const SOLANA_TESTNET_RPC_URL: &str = "https://api.testnet.solana.com";
+pub const SOLANA_LOCALNET_RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// The Solana clusters defined by the wallet standard.
+///
+/// ```
+/// use wallet_standard::SolanaChain;
+///
+/// let chain: SolanaChain = "solana:devnet".parse()?;
+///
+/// assert_eq!(chain, SolanaChain::Devnet);
+/// assert_eq!(chain.as_str(), "solana:devnet");
+/// assert_eq!(chain.rpc_url(), "https://api.devnet.solana.com");
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum SolanaChain {
+	Mainnet,
+	Devnet,
+	Testnet,
+	Localnet,
+}
+
+impl SolanaChain {
+	/// Every chain, in the order they are usually presented.
+	pub const ALL: [Self; 4] = [Self::Mainnet, Self::Devnet, Self::Testnet, Self::Localnet];
+
+	/// The wallet standard chain, e.g. `solana:mainnet`.
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Mainnet => SOLANA_MAINNET_CHAIN,
+			Self::Devnet => SOLANA_DEVNET_CHAIN,
+			Self::Testnet => SOLANA_TESTNET_CHAIN,
+			Self::Localnet => SOLANA_LOCALNET_CHAIN,
+		}
+	}
+
+	/// The base58 encoded genesis hash of the cluster. Local clusters don't
+	/// have a canonical genesis hash.
+	pub const fn genesis_hash(self) -> Option<&'static str> {
+		match self {
+			Self::Mainnet => Some(SOLANA_MAINNET_GENESIS_HASH),
+			Self::Devnet => Some(SOLANA_DEVNET_GENESIS_HASH),
+			Self::Testnet => Some(SOLANA_TESTNET_GENESIS_HASH),
+			Self::Localnet => None,
+		}
+	}
+
+	/// The default public RPC url of the cluster.
+	pub const fn rpc_url(self) -> &'static str {
+		match self {
+			Self::Mainnet => SOLANA_MAINNET_RPC_URL,
+			Self::Devnet => SOLANA_DEVNET_RPC_URL,
+			Self::Testnet => SOLANA_TESTNET_RPC_URL,
+			Self::Localnet => SOLANA_LOCALNET_RPC_URL,
+		}
+	}
+
+	/// The chain as a typed [`ChainId`].
+	pub const fn chain_id(self) -> ChainId {
+		ChainId::from_static(self.as_str())
+	}
+
+	/// The CAIP-2 chain id of the cluster, when it has a canonical genesis
+	/// hash.
+	pub fn caip2(self) -> Option<Caip2ChainId> {
+		self.genesis_hash()
+			.and_then(|genesis_hash| Caip2ChainId::solana(genesis_hash).ok())
+	}
+
+	/// Find the cluster with the provided base58 encoded genesis hash.
+	pub fn from_genesis_hash(genesis_hash: &str) -> Option<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|chain| chain.genesis_hash() == Some(genesis_hash))
+	}
+
+	/// Parse a wallet standard chain such as `solana:mainnet`.
+	pub fn parse(value: &str) -> WalletResult<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|chain| chain.as_str() == value)
+			.ok_or_else(|| {
+				WalletError::InvalidIdentifier(format!("`{value}`: not a known solana chain"))
+			})
+	}
+}
+
+impl fmt::Display for SolanaChain {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for SolanaChain {
+	type Err = WalletError;
+
+	fn from_str(value: &str) -> WalletResult<Self> {
+		Self::parse(value)
+	}
+}
+
+impl TryFrom<&str> for SolanaChain {
+	type Error = WalletError;
+
+	fn try_from(value: &str) -> WalletResult<Self> {
+		Self::parse(value)
+	}
+}
+
+impl TryFrom<String> for SolanaChain {
+	type Error = WalletError;
+
+	fn try_from(value: String) -> WalletResult<Self> {
+		Self::parse(&value)
+	}
+}
+
+impl TryFrom<&ChainId> for SolanaChain {
+	type Error = WalletError;
+
+	fn try_from(value: &ChainId) -> WalletResult<Self> {
+		Self::parse(value.as_str())
+	}
+}
+
+impl TryFrom<&Caip2ChainId> for SolanaChain {
+	type Error = WalletError;
+
+	fn try_from(value: &Caip2ChainId) -> WalletResult<Self> {
+		Self::try_from(&value.to_chain_id()?)
+	}
+}
+
+impl From<SolanaChain> for &'static str {
+	fn from(value: SolanaChain) -> Self {
+		value.as_str()
+	}
+}
+
+impl From<SolanaChain> for String {
+	fn from(value: SolanaChain) -> Self {
+		value.as_str().to_string()
+	}
+}
+
+impl From<SolanaChain> for ChainId {
+	fn from(value: SolanaChain) -> Self {
+		value.chain_id()
+	}
+}
+
+/// Lets a chain be passed to the `chain` setters which take an
+/// `Option<String>`.
+impl From<SolanaChain> for Option<String> {
+	fn from(value: SolanaChain) -> Self {
+		Some(value.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use solana_message::Message;
+	use solana_transaction::Transaction;
+	use solana_transaction::versioned::VersionedTransaction;
+
+	use super::*;
+	use crate::SolanaSignAndSendTransactionProps;
+	use crate::SolanaSignTransactionProps;
+
+	#[test]
+	fn chains_round_trip_through_strings() -> WalletResult<()> {
+		for chain in SolanaChain::ALL {
+			assert_eq!(SolanaChain::parse(&chain.to_string())?, chain);
+			assert_eq!(SolanaChain::try_from(&chain.chain_id())?, chain);
+		}
+
+		assert!(SolanaChain::parse("solana:mainnet-beta").is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn genesis_hashes_match_caip2() {
+		for chain in SolanaChain::ALL {
+			let Some(genesis_hash) = chain.genesis_hash() else {
+				assert_eq!(chain, SolanaChain::Localnet);
+				continue;
+			};
+
+			let caip2 = chain.caip2().unwrap();
+
+			assert_eq!(SolanaChain::from_genesis_hash(genesis_hash), Some(chain));
+			assert_eq!(SolanaChain::try_from(&caip2).unwrap(), chain);
+			assert_eq!(caip2.to_chain_id().unwrap(), chain.chain_id());
+			assert_eq!(
+				Caip2ChainId::from_wallet_chain(chain.as_str()).unwrap(),
+				caip2
+			);
+		}
+	}
+
+	#[test]
+	fn typed_chain_setters() {
+		let transaction =
+			VersionedTransaction::from(Transaction::new_unsigned(Message::new(&[], None)));
+		let props = SolanaSignTransactionProps::builder()
+			.transaction(transaction.clone())
+			.chain(SolanaChain::Devnet)
+			.build();
+
+		assert_eq!(props.chain.as_deref(), Some(SOLANA_DEVNET_CHAIN));
+
+		let props = SolanaSignTransactionProps::builder()
+			.transaction(transaction.clone())
+			.chain(Some(SOLANA_TESTNET_CHAIN.to_string()))
+			.build();
+
+		assert_eq!(props.chain.as_deref(), Some(SOLANA_TESTNET_CHAIN));
+
+		let props = SolanaSignAndSendTransactionProps::builder()
+			.transaction(transaction)
+			.chain(SolanaChain::Mainnet)
+			.build();
+
+		assert_eq!(props.chain.as_deref(), Some(SOLANA_MAINNET_CHAIN));
+	}
+}
@@ -0,0 +1,905 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_keypair::Keypair;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use solana_signer::Signer;
+use solana_transaction::versioned::VersionedTransaction;
+use typed_builder::TypedBuilder;
+
+use crate::ConnectedWalletStandardEvents;
+use crate::SOLANA_SIGN_AND_SEND_TRANSACTION;
+use crate::SOLANA_SIGN_IN;
+use crate::SOLANA_SIGN_MESSAGE;
+use crate::SOLANA_SIGN_TRANSACTION;
+use crate::STANDARD_CONNECT;
+use crate::STANDARD_DISCONNECT;
+use crate::STANDARD_EVENTS;
+use crate::SolanaChain;
+use crate::SolanaSignAndSendTransactionProps;
+use crate::SolanaSignInInput;
+use crate::SolanaSignInOutput;
+use crate::SolanaSignMessageOutput;
+use crate::SolanaSignTransactionProps;
+use crate::SolanaSignatureOutput;
+use crate::StandardConnectInput;
+use crate::StandardEventsChange;
+use crate::StandardEventsListener;
+use crate::Wallet;
+use crate::WalletAccountInfo;
+use crate::WalletAccounts;
+use crate::WalletError;
+use crate::WalletEventEmitter;
+use crate::WalletEventStream;
+use crate::WalletInfo;
+use crate::WalletResult;
+use crate::WalletSolanaPubkey;
+use crate::create_sign_in_message_text;
+use crate::reconcile_wallet_account;
+use crate::send::WalletSolanaSignAndSendTransaction;
+use crate::send::WalletSolanaSignIn;
+use crate::send::WalletSolanaSignMessage;
+use crate::send::WalletSolanaSignTransaction;
+use crate::send::WalletStandardConnect;
+use crate::send::WalletStandardDisconnect;
+
+/// The default name of a [`KeypairWallet`].
+pub const KEYPAIR_WALLET_NAME: &str = "Keypair Wallet";
+
+/// The default icon of a [`KeypairWallet`].
+pub const KEYPAIR_WALLET_ICON: &str = "data:image/svg+xml;base64,PHN2ZyB4bWxucz0iaHR0cDovL3d3dy53My5vcmcvMjAwMC9zdmciIHdpZHRoPSIzMiIgaGVpZ2h0PSIzMiIgdmlld0JveD0iMCAwIDMyIDMyIj48cmVjdCB3aWR0aD0iMzIiIGhlaWdodD0iMzIiIHJ4PSI4IiBmaWxsPSIjNDE0MTQxIi8+PC9zdmc+";
+
+/// The version of the Wallet Standard implemented by a [`KeypairWallet`].
+pub const KEYPAIR_WALLET_VERSION: &str = "1.0.0";
+
+/// An in-memory wallet backed by one or more [`Keypair`]s.
+///
+/// `KeypairWallet` implements every trait of [`crate::WalletSolana`], which
+/// makes it possible for native code (backend services, command line tools and
+/// tests) to use the same trait-based code paths as a browser wallet. The
+/// wallet implements the [`crate::send`] traits, so its futures can also be
+/// awaited from multi-threaded runtimes.
+///
+/// Accounts are only exposed once the app has connected. A silent connection
+/// returns the previously authorized accounts without authorizing new ones.
+///
+/// [`WalletSolanaSignAndSendTransaction`] signs the transaction and returns its
+/// signature. The wallet has no RPC connection, so the transaction is not
+/// submitted to the network.
+///
+/// # Example
+///
+/// ```rust
+/// use solana_keypair::Keypair;
+/// use wallet_standard::KeypairWallet;
+/// use wallet_standard::prelude::*;
+///
+/// # futures::executor::block_on(async {
+/// let mut wallet = KeypairWallet::builder()
+/// 	.keypairs(vec![Keypair::new()])
+/// 	.build();
+///
+/// wallet.connect().await?;
+/// let output = wallet.sign_message_async("hello").await?;
+/// assert!(
+/// 	output
+/// 		.signature()
+/// 		.verify(&wallet.solana_pubkey().to_bytes(), &output.signed_message())
+/// );
+/// # WalletResult::Ok(())
+/// # })
+/// # .unwrap();
+/// ```
+#[derive(Clone, Debug, TypedBuilder)]
+pub struct KeypairWallet {
+	/// The keypairs held by the wallet. Each keypair is exposed as an account.
+	#[builder(setter(transform = |keypairs: Vec<Keypair>| keypairs.into_iter().map(Arc::new).collect()))]
+	keypairs: Vec<Arc<Keypair>>,
+	/// The name of the wallet.
+	#[builder(default = KEYPAIR_WALLET_NAME.to_string(), setter(into))]
+	name: String,
+	/// The icon of the wallet as a data URL.
+	#[builder(default = KEYPAIR_WALLET_ICON.to_string(), setter(into))]
+	icon: String,
+	/// The chains supported by the wallet and its accounts.
+	#[builder(default = default_chains())]
+	chains: Vec<String>,
+	/// Whether the app has been authorized to use the accounts.
+	#[builder(default, setter(skip))]
+	authorized: bool,
+	/// The currently selected wallet account.
+	#[builder(default, setter(skip))]
+	wallet_account: Option<KeypairWalletAccount>,
+	/// Emits `change` events when the authorized accounts change.
+	#[builder(default, setter(skip))]
+	events: WalletEventEmitter<KeypairWalletAccount>,
+}
+
+fn default_chains() -> Vec<String> {
+	SolanaChain::ALL.into_iter().map(String::from).collect()
+}
+
+impl KeypairWallet {
+	/// Create a wallet holding a single keypair with the default configuration.
+	pub fn new(keypair: Keypair) -> Self {
+		Self::builder().keypairs(vec![keypair]).build()
+	}
+
+	/// The features supported by the wallet.
+	pub fn wallet_features() -> Vec<String> {
+		vec![
+			STANDARD_CONNECT.to_string(),
+			STANDARD_DISCONNECT.to_string(),
+			STANDARD_EVENTS.to_string(),
+			SOLANA_SIGN_MESSAGE.to_string(),
+			SOLANA_SIGN_TRANSACTION.to_string(),
+			SOLANA_SIGN_AND_SEND_TRANSACTION.to_string(),
+			SOLANA_SIGN_IN.to_string(),
+		]
+	}
+
+	/// The features supported by each account of the wallet.
+	pub fn account_features() -> Vec<String> {
+		vec![
+			SOLANA_SIGN_MESSAGE.to_string(),
+			SOLANA_SIGN_TRANSACTION.to_string(),
+			SOLANA_SIGN_AND_SEND_TRANSACTION.to_string(),
+			SOLANA_SIGN_IN.to_string(),
+		]
+	}
+
+	/// Whether the app is currently authorized to use the accounts.
+	pub fn authorized(&self) -> bool {
+		self.authorized
+	}
+
+	/// The accounts for every keypair held by the wallet, whether or not the
+	/// app has been authorized to use them.
+	pub fn all_accounts(&self) -> Vec<KeypairWalletAccount> {
+		self.keypairs
+			.iter()
+			.map(|keypair| self.account_for(keypair))
+			.collect()
+	}
+
+	/// Add a keypair to the wallet. The new account is authorized straight
+	/// away when the app is already connected.
+	pub fn add_keypair(&mut self, keypair: Keypair) {
+		if self.keypair_for(&keypair.pubkey()).is_none() {
+			let previous = self.wallet();
+			self.keypairs.push(Arc::new(keypair));
+			self.emit_change(&previous);
+		}
+	}
+
+	/// Remove the keypair from the wallet, returning whether it was held.
+	///
+	/// When the active account is removed the next remaining account becomes
+	/// active.
+	pub fn remove_keypair(&mut self, pubkey: &Pubkey) -> bool {
+		let previous = self.wallet();
+		let len = self.keypairs.len();
+		self.keypairs.retain(|keypair| keypair.pubkey() != *pubkey);
+		self.sync_wallet_account();
+		self.emit_change(&previous);
+
+		self.keypairs.len() != len
+	}
+
+	/// The emitter used for `standard:events`.
+	pub fn events(&self) -> &WalletEventEmitter<KeypairWalletAccount> {
+		&self.events
+	}
+
+	/// A stream of the changes to the wallet, starting from the currently
+	/// authorized accounts.
+	pub fn event_stream(&self) -> WalletEventStream<KeypairWalletAccount> {
+		self.events.stream(self.wallet().accounts())
+	}
+
+	fn emit_change(&self, previous: &KeypairWalletInfo) {
+		self.events
+			.emit(&StandardEventsChange::between(previous, &self.wallet()));
+	}
+
+	fn account_for(&self, keypair: &Keypair) -> KeypairWalletAccount {
+		KeypairWalletAccount::builder()
+			.pubkey(keypair.pubkey())
+			.chains(self.chains.clone())
+			.features(Self::account_features())
+			.build()
+	}
+
+	fn keypair_for(&self, pubkey: &Pubkey) -> Option<&Keypair> {
+		self.keypairs
+			.iter()
+			.find(|keypair| keypair.pubkey() == *pubkey)
+			.map(AsRef::as_ref)
+	}
+
+	/// The keypair of the currently selected account.
+	fn active_keypair(&self) -> WalletResult<&Keypair> {
+		let account = self
+			.wallet_account
+			.as_ref()
+			.ok_or(WalletError::WalletAccount)?;
+
+		self.keypair_for(&account.pubkey)
+			.ok_or(WalletError::WalletAccount)
+	}
+
+	fn check_chain(&self, chain: Option<&String>) -> WalletResult<()> {
+		match chain {
+			Some(chain) if !self.chains.contains(chain) => Err(WalletError::InvalidArguments),
+			_ => Ok(()),
+		}
+	}
+}
+
+impl From<Keypair> for KeypairWallet {
+	fn from(keypair: Keypair) -> Self {
+		Self::new(keypair)
+	}
+}
+
+/// Sign a [`VersionedTransaction`] with the provided keypair.
+///
+/// Both legacy and v0 transactions are supported. The keypair must be one of
+/// the required signers of the transaction message, and any existing
+/// signatures from other signers are preserved.
+pub fn sign_versioned_transaction(
+	keypair: &Keypair,
+	mut transaction: VersionedTransaction,
+) -> WalletResult<VersionedTransaction> {
+	let pubkey = keypair.pubkey();
+	let required_signatures = usize::from(transaction.message.header().num_required_signatures);
+	let index = transaction
+		.message
+		.static_account_keys()
+		.iter()
+		.take(required_signatures)
+		.position(|key| *key == pubkey)
+		.ok_or(WalletError::WalletSignTransaction)?;
+
+	if transaction.signatures.len() < required_signatures {
+		transaction
+			.signatures
+			.resize(required_signatures, Signature::default());
+	}
+
+	transaction.signatures[index] =
+		Signer::try_sign_message(keypair, &transaction.message.serialize())?;
+
+	Ok(transaction)
+}
+
+/// Information about a [`KeypairWallet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeypairWalletInfo {
+	name: String,
+	icon: String,
+	chains: Vec<String>,
+	accounts: Vec<KeypairWalletAccount>,
+}
+
+impl WalletInfo for KeypairWalletInfo {
+	type Account = KeypairWalletAccount;
+
+	fn version(&self) -> String {
+		KEYPAIR_WALLET_VERSION.to_string()
+	}
+
+	fn name(&self) -> String {
+		self.name.clone()
+	}
+
+	fn icon(&self) -> String {
+		self.icon.clone()
+	}
+
+	fn chains(&self) -> Vec<String> {
+		self.chains.clone()
+	}
+
+	fn features(&self) -> Vec<String> {
+		KeypairWallet::wallet_features()
+	}
+
+	fn accounts(&self) -> Vec<Self::Account> {
+		self.accounts.clone()
+	}
+}
+
+/// An account of a [`KeypairWallet`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, TypedBuilder)]
+pub struct KeypairWalletAccount {
+	/// The public key of the keypair backing this account.
+	pubkey: Pubkey,
+	/// Chains supported by the account.
+	#[builder(default)]
+	chains: Vec<String>,
+	/// Feature names supported by the account.
+	#[builder(default)]
+	features: Vec<String>,
+	/// Optional label for the account.
+	#[builder(default, setter(into, strip_option))]
+	label: Option<String>,
+	/// Optional icon for the account as a data URL.
+	#[builder(default, setter(into, strip_option))]
+	icon: Option<String>,
+}
+
+impl WalletAccountInfo for KeypairWalletAccount {
+	fn address(&self) -> String {
+		self.pubkey.to_string()
+	}
+
+	fn public_key(&self) -> Vec<u8> {
+		self.pubkey.to_bytes().to_vec()
+	}
+
+	fn chains(&self) -> Vec<String> {
+		self.chains.clone()
+	}
+
+	fn features(&self) -> Vec<String> {
+		self.features.clone()
+	}
+
+	fn label(&self) -> Option<String> {
+		self.label.clone()
+	}
+
+	fn icon(&self) -> Option<String> {
+		self.icon.clone()
+	}
+}
+
+impl WalletSolanaPubkey for KeypairWalletAccount {
+	fn try_solana_pubkey(&self) -> WalletResult<Pubkey> {
+		Ok(self.pubkey)
+	}
+}
+
+/// The output of signing in with a [`KeypairWallet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeypairSignInOutput {
+	/// The account that was signed in.
+	pub account: KeypairWalletAccount,
+	/// The sign in message text, as raw bytes.
+	pub signed_message: Vec<u8>,
+	/// The signature of the `signed_message`.
+	pub signature: Signature,
+}
+
+impl SolanaSignatureOutput for KeypairSignInOutput {
+	fn try_signature(&self) -> WalletResult<Signature> {
+		Ok(self.signature)
+	}
+
+	fn signature(&self) -> Signature {
+		self.signature
+	}
+}
+
+impl SolanaSignMessageOutput for KeypairSignInOutput {
+	fn signed_message(&self) -> Vec<u8> {
+		self.signed_message.clone()
+	}
+
+	fn signature_type(&self) -> Option<String> {
+		None
+	}
+}
+
+impl SolanaSignInOutput for KeypairSignInOutput {
+	type Account = KeypairWalletAccount;
+
+	fn account(&self) -> Self::Account {
+		self.account.clone()
+	}
+}
+
+impl Wallet for KeypairWallet {
+	type Account = KeypairWalletAccount;
+	type Wallet = KeypairWalletInfo;
+
+	fn wallet(&self) -> Self::Wallet {
+		let accounts = if self.authorized {
+			self.all_accounts()
+		} else {
+			vec![]
+		};
+
+		KeypairWalletInfo {
+			name: self.name.clone(),
+			icon: self.icon.clone(),
+			chains: self.chains.clone(),
+			accounts,
+		}
+	}
+
+	fn wallet_account(&self) -> Option<Self::Account> {
+		self.wallet_account.clone()
+	}
+}
+
+impl WalletAccounts for KeypairWallet {
+	fn wallet_accounts(&self) -> Vec<Self::Account> {
+		self.wallet().accounts()
+	}
+
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account> {
+		let account = self.find_account(address)?;
+		self.wallet_account = Some(account.clone());
+
+		Ok(account)
+	}
+
+	fn sync_wallet_account(&mut self) -> Option<Self::Account> {
+		self.wallet_account =
+			reconcile_wallet_account(self.wallet_account.as_ref(), &self.wallet_accounts());
+		self.wallet_account.clone()
+	}
+}
+
+impl ConnectedWalletStandardEvents for KeypairWallet {
+	type Callback = StandardEventsListener<KeypairWalletAccount>;
+
+	fn on(&self, event: impl AsRef<str>, callback: &Self::Callback) -> WalletResult<Box<dyn Fn()>> {
+		self.events.on(event, callback)
+	}
+}
+
+#[async_trait]
+impl WalletStandardConnect for KeypairWallet {
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>> {
+		self.connect_with_options(StandardConnectInput::default())
+			.await
+	}
+
+	/// A silent connection only returns accounts which have already been
+	/// authorized, while an interactive connection authorizes every keypair
+	/// held by the wallet.
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>> {
+		if options.silent.unwrap_or(false) && !self.authorized {
+			return Ok(vec![]);
+		}
+
+		let previous = self.wallet();
+		let accounts = self.all_accounts();
+		let account = accounts
+			.first()
+			.cloned()
+			.ok_or(WalletError::WalletConnection)?;
+
+		self.authorized = true;
+
+		if self.wallet_account.is_none() {
+			self.wallet_account = Some(account);
+		}
+
+		self.emit_change(&previous);
+
+		Ok(accounts)
+	}
+}
+
+#[async_trait]
+impl WalletStandardDisconnect for KeypairWallet {
+	async fn disconnect(&mut self) -> WalletResult<()> {
+		if !self.connected() {
+			return Err(WalletError::WalletDisconnected);
+		}
+
+		let previous = self.wallet();
+		self.authorized = false;
+		self.wallet_account = None;
+		self.emit_change(&previous);
+
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignMessage for KeypairWallet {
+	type Output = (Signature, Vec<u8>, Option<String>);
+
+	async fn sign_message_async(
+		&self,
+		message: impl Into<Vec<u8>> + Send,
+	) -> WalletResult<Self::Output> {
+		let message: Vec<u8> = message.into();
+		let signature = Signer::try_sign_message(self.active_keypair()?, &message)?;
+
+		Ok((signature, message, None))
+	}
+
+	async fn sign_messages<M: Into<Vec<u8>> + Send>(
+		&self,
+		messages: Vec<M>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(messages.len());
+
+		for message in messages {
+			outputs.push(self.sign_message_async(message).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignTransaction for KeypairWallet {
+	type Output = VersionedTransaction;
+
+	async fn sign_transaction(
+		&self,
+		props: SolanaSignTransactionProps,
+	) -> WalletResult<Self::Output> {
+		let keypair = self.active_keypair()?;
+		self.check_chain(props.chain.as_ref())?;
+
+		sign_versioned_transaction(keypair, props.transaction)
+	}
+
+	async fn sign_transactions(
+		&self,
+		inputs: Vec<SolanaSignTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for props in inputs {
+			outputs.push(self.sign_transaction(props).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignAndSendTransaction for KeypairWallet {
+	type Output = Signature;
+
+	async fn sign_and_send_transaction(
+		&self,
+		props: SolanaSignAndSendTransactionProps,
+	) -> WalletResult<Self::Output> {
+		let keypair = self.active_keypair()?;
+		self.check_chain(props.chain.as_ref())?;
+
+		let transaction = sign_versioned_transaction(keypair, props.transaction)?;
+
+		transaction
+			.signatures
+			.first()
+			.copied()
+			.ok_or(WalletError::WalletSendTransaction)
+	}
+
+	async fn sign_and_send_transactions(
+		&self,
+		inputs: Vec<SolanaSignAndSendTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for props in inputs {
+			outputs.push(self.sign_and_send_transaction(props).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignIn for KeypairWallet {
+	type Output = KeypairSignInOutput;
+
+	/// Sign in with the account matching `input.address`. When no address is
+	/// provided the currently selected account is used, falling back to the
+	/// first keypair held by the wallet.
+	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
+		let keypair = match input.address.as_ref() {
+			Some(address) => {
+				let pubkey = address
+					.parse::<Pubkey>()
+					.map_err(|_| WalletError::WalletPublicKey)?;
+				self.keypair_for(&pubkey)
+					.ok_or(WalletError::WalletAccount)?
+			}
+			None => {
+				self.active_keypair()
+					.ok()
+					.or_else(|| self.keypairs.first().map(AsRef::as_ref))
+					.ok_or(WalletError::WalletAccount)?
+			}
+		};
+
+		let account = self.account_for(keypair);
+		self.check_chain(input.chain_id.as_ref())?;
+
+		let input = SolanaSignInInput {
+			address: Some(account.address()),
+			..input
+		};
+		let signed_message = create_sign_in_message_text(&input)?.into_bytes();
+		let signature = Signer::try_sign_message(keypair, &signed_message)?;
+
+		Ok(KeypairSignInOutput {
+			account,
+			signed_message,
+			signature,
+		})
+	}
+
+	async fn sign_in_many(
+		&self,
+		inputs: Vec<SolanaSignInInput>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for input in inputs {
+			outputs.push(self.sign_in(input).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+/// A wallet with the `keypairs` which has already been connected, shared by
+/// the tests of the sign in modules.
+#[cfg(test)]
+pub(crate) fn connected_wallet(keypairs: Vec<Keypair>) -> KeypairWallet {
+	let mut wallet = KeypairWallet::builder().keypairs(keypairs).build();
+	futures::executor::block_on(wallet.connect()).unwrap();
+	wallet
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+	use solana_message::Hash;
+	use solana_message::Message;
+	use solana_message::VersionedMessage;
+	use solana_message::v0;
+	use solana_pubkey::Pubkey;
+	use solana_transaction::Transaction;
+
+	use super::*;
+	use crate::SignInVerificationError;
+	use crate::verify_sign_in;
+
+	#[test]
+	fn silent_connect_requires_prior_authorization() -> WalletResult<()> {
+		let mut wallet = KeypairWallet::new(Keypair::new());
+		let silent = StandardConnectInput::builder().silent(true).build();
+
+		assert!(block_on(wallet.connect_with_options(silent))?.is_empty());
+		assert!(!wallet.connected());
+		assert!(wallet.wallet().accounts().is_empty());
+
+		block_on(wallet.connect())?;
+		block_on(wallet.disconnect())?;
+		assert!(!wallet.connected());
+		assert!(matches!(
+			block_on(wallet.disconnect()),
+			Err(WalletError::WalletDisconnected)
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn connect_exposes_every_keypair() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new(), Keypair::new()]);
+		let accounts = wallet.wallet().accounts();
+
+		assert_eq!(accounts.len(), 2);
+		assert_eq!(wallet.wallet_account(), accounts.first().cloned());
+
+		let silent = StandardConnectInput::builder().silent(true).build();
+		let mut wallet = wallet;
+		assert_eq!(block_on(wallet.connect_with_options(silent))?, accounts);
+
+		Ok(())
+	}
+
+	#[test]
+	fn switch_and_remove_accounts() -> WalletResult<()> {
+		let first = Keypair::new();
+		let second = Keypair::new();
+		let (first_pubkey, second_pubkey) = (first.pubkey(), second.pubkey());
+		let mut wallet = connected_wallet(vec![first, second]);
+
+		assert!(matches!(
+			wallet.select_account("unknown"),
+			Err(WalletError::WalletAccountNotFound(_))
+		));
+
+		let other = wallet.with_account(&second_pubkey.to_string())?;
+		assert_eq!(other.solana_pubkey(), second_pubkey);
+		assert_eq!(wallet.solana_pubkey(), first_pubkey);
+
+		wallet.select_account(&second_pubkey.to_string())?;
+		assert_eq!(wallet.solana_pubkey(), second_pubkey);
+
+		assert!(wallet.remove_keypair(&second_pubkey));
+		assert_eq!(wallet.solana_pubkey(), first_pubkey);
+		assert!(wallet.remove_keypair(&first_pubkey));
+		assert!(!wallet.connected());
+
+		Ok(())
+	}
+
+	#[test]
+	fn emits_account_changes() -> WalletResult<()> {
+		let mut wallet = KeypairWallet::new(Keypair::new());
+		let changes = Arc::new(std::sync::Mutex::new(vec![]));
+		let _subscription = wallet.events().subscribe({
+			let changes = changes.clone();
+			move |change| changes.lock().unwrap().push(change.accounts.clone())
+		});
+
+		let accounts = block_on(wallet.connect())?;
+		let keypair = Keypair::new();
+		let pubkey = keypair.pubkey();
+		wallet.add_keypair(keypair);
+		wallet.remove_keypair(&pubkey);
+		block_on(wallet.disconnect())?;
+
+		let changes = changes.lock().unwrap();
+		assert_eq!(changes.len(), 4);
+		assert_eq!(changes[0], Some(accounts.clone()));
+		assert_eq!(changes[1].as_ref().map(Vec::len), Some(2));
+		assert_eq!(changes[2], Some(accounts));
+		assert_eq!(changes[3], Some(vec![]));
+
+		Ok(())
+	}
+
+	#[test]
+	fn event_stream_reports_added_and_removed_accounts() -> WalletResult<()> {
+		use futures::StreamExt;
+
+		use crate::WalletEvent;
+
+		let mut wallet = KeypairWallet::new(Keypair::new());
+		let mut events = wallet.event_stream();
+
+		let accounts = block_on(wallet.connect())?;
+		block_on(wallet.disconnect())?;
+
+		assert_eq!(
+			block_on(events.next()),
+			Some(WalletEvent::AccountsChanged {
+				added: accounts.clone(),
+				removed: vec![],
+			})
+		);
+		assert_eq!(
+			block_on(events.next()),
+			Some(WalletEvent::AccountsChanged {
+				added: vec![],
+				removed: accounts,
+			})
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn signs_legacy_and_v0_transactions() -> WalletResult<()> {
+		let keypair = Keypair::new();
+		let payer = keypair.pubkey();
+		let wallet = connected_wallet(vec![keypair]);
+
+		let legacy =
+			VersionedTransaction::from(Transaction::new_unsigned(Message::new(&[], Some(&payer))));
+		let message = v0::Message::try_compile(&payer, &[], &[], Hash::default()).unwrap();
+		let v0 = VersionedTransaction {
+			signatures: vec![],
+			message: VersionedMessage::V0(message),
+		};
+
+		for transaction in [legacy, v0] {
+			let props = SolanaSignTransactionProps::builder()
+				.transaction(transaction)
+				.build();
+			let signed = block_on(wallet.sign_transaction(props))?;
+
+			let signature = signed.signatures.first().unwrap();
+
+			assert!(signature.verify(payer.as_ref(), &signed.message.serialize()));
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn sign_transaction_rejects_non_signers() {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let transaction = VersionedTransaction::from(Transaction::new_unsigned(Message::new(
+			&[],
+			Some(&Pubkey::new_unique()),
+		)));
+		let props = SolanaSignTransactionProps::builder()
+			.transaction(transaction)
+			.build();
+
+		assert_eq!(
+			block_on(wallet.sign_transaction(props)),
+			Err(WalletError::WalletSignTransaction)
+		);
+	}
+
+	#[test]
+	fn sign_transactions_each_reports_the_failing_index() {
+		let keypair = Keypair::new();
+		let payer = keypair.pubkey();
+		let wallet = connected_wallet(vec![keypair]);
+		let props = |payer: &Pubkey| {
+			SolanaSignTransactionProps::builder()
+				.transaction(VersionedTransaction::from(Transaction::new_unsigned(
+					Message::new(&[], Some(payer)),
+				)))
+				.build()
+		};
+
+		let error = block_on(wallet.sign_transactions_each(vec![
+			props(&payer),
+			props(&Pubkey::new_unique()),
+			props(&payer),
+		]))
+		.unwrap_err();
+
+		assert_eq!(error.index, 1);
+		assert_eq!(error.error, WalletError::WalletSignTransaction);
+		assert_eq!(error.completed.len(), 1);
+	}
+
+	#[test]
+	fn sign_in_output_passes_verification() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.address(wallet.solana_pubkey().to_string())
+			.statement("Sign in to the example app")
+			.nonce("abcdef12")
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+
+		verify_sign_in(&input, &output)
+	}
+
+	#[test]
+	fn sign_in_verification_checks_resources() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.address(wallet.solana_pubkey().to_string())
+			.resources(vec!["https://example.com/a".into()])
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+		verify_sign_in(&input, &output)?;
+
+		let other = SolanaSignInInput {
+			resources: Some(vec!["https://example.com/b".into()]),
+			..input
+		};
+		assert_eq!(
+			verify_sign_in(&other, &output),
+			Err(WalletError::WalletSignInVerification(
+				SignInVerificationError::ResourcesMismatch {
+					expected: Some(vec!["https://example.com/b".into()]),
+					received: Some(vec!["https://example.com/a".into()]),
+				}
+			))
+		);
+
+		Ok(())
+	}
+}
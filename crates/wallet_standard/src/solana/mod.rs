@@ -1,11 +1,24 @@
+pub use chain::*;
+pub use keypair_wallet::*;
 pub use sign_and_send_transaction::*;
 pub use sign_in::*;
+pub use sign_in_fallback::*;
+pub use sign_in_fields::*;
+pub use sign_in_message::*;
+pub use sign_in_verifier::*;
 pub use sign_message::*;
 pub use sign_transaction::*;
 pub use types::*;
 
+mod chain;
+mod keypair_wallet;
+pub(crate) mod send;
 mod sign_and_send_transaction;
 mod sign_in;
+mod sign_in_fallback;
+mod sign_in_fields;
+mod sign_in_message;
+mod sign_in_verifier;
 mod sign_message;
 mod sign_transaction;
 mod types;
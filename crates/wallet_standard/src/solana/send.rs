@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+
+use crate::BatchResult;
+use crate::SolanaSignAndSendTransactionOutput;
+use crate::SolanaSignAndSendTransactionProps;
+use crate::SolanaSignInInput;
+use crate::SolanaSignInOutput;
+use crate::SolanaSignMessageOutput;
+use crate::SolanaSignTransactionOutput;
+use crate::SolanaSignTransactionProps;
+use crate::WalletResult;
+use crate::send::WalletStandard;
+use crate::try_batch;
+
+/// The `Send` version of [`crate::WalletSolanaSignMessage`].
+#[async_trait]
+pub trait WalletSolanaSignMessage: Send + Sync {
+	type Output: SolanaSignMessageOutput + Send;
+
+	/// Sign a message using the account's secret key.
+	async fn sign_message_async(
+		&self,
+		message: impl Into<Vec<u8>> + Send,
+	) -> WalletResult<Self::Output>;
+
+	/// Sign multiple messages using the account's secret key.
+	async fn sign_messages<M: Into<Vec<u8>> + Send>(
+		&self,
+		messages: Vec<M>,
+	) -> WalletResult<Vec<Self::Output>>;
+
+	/// Sign each of the messages in order. See
+	/// [`crate::WalletSolanaSignMessage::sign_messages_each`].
+	async fn sign_messages_each<M: Into<Vec<u8>> + Send>(
+		&self,
+		messages: Vec<M>,
+	) -> BatchResult<Self::Output> {
+		try_batch(messages, |message| self.sign_message_async(message)).await
+	}
+}
+
+#[async_trait(?Send)]
+impl<T: WalletSolanaSignMessage> crate::WalletSolanaSignMessage for T {
+	type Output = <T as WalletSolanaSignMessage>::Output;
+
+	async fn sign_message_async(&self, message: impl Into<Vec<u8>>) -> WalletResult<Self::Output> {
+		let message: Vec<u8> = message.into();
+		WalletSolanaSignMessage::sign_message_async(self, message).await
+	}
+
+	async fn sign_messages<M: Into<Vec<u8>>>(
+		&self,
+		messages: Vec<M>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let messages: Vec<Vec<u8>> = messages.into_iter().map(Into::into).collect();
+		WalletSolanaSignMessage::sign_messages(self, messages).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletSolanaSignTransaction`].
+#[async_trait]
+pub trait WalletSolanaSignTransaction: Send + Sync {
+	type Output: SolanaSignTransactionOutput + Send;
+
+	async fn sign_transaction(
+		&self,
+		props: SolanaSignTransactionProps,
+	) -> WalletResult<Self::Output>;
+	async fn sign_transactions(
+		&self,
+		inputs: Vec<SolanaSignTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>>;
+
+	/// Sign each of the transactions in order. See
+	/// [`crate::WalletSolanaSignTransaction::sign_transactions_each`].
+	async fn sign_transactions_each(
+		&self,
+		inputs: Vec<SolanaSignTransactionProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(inputs, |props| self.sign_transaction(props)).await
+	}
+}
+
+#[async_trait(?Send)]
+impl<T: WalletSolanaSignTransaction> crate::WalletSolanaSignTransaction for T {
+	type Output = <T as WalletSolanaSignTransaction>::Output;
+
+	async fn sign_transaction(
+		&self,
+		props: SolanaSignTransactionProps,
+	) -> WalletResult<Self::Output> {
+		WalletSolanaSignTransaction::sign_transaction(self, props).await
+	}
+
+	async fn sign_transactions(
+		&self,
+		inputs: Vec<SolanaSignTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		WalletSolanaSignTransaction::sign_transactions(self, inputs).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletSolanaSignAndSendTransaction`].
+#[async_trait]
+pub trait WalletSolanaSignAndSendTransaction: Send + Sync {
+	type Output: SolanaSignAndSendTransactionOutput + Send;
+
+	async fn sign_and_send_transaction(
+		&self,
+		props: SolanaSignAndSendTransactionProps,
+	) -> WalletResult<Self::Output>;
+	async fn sign_and_send_transactions(
+		&self,
+		inputs: Vec<SolanaSignAndSendTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>>;
+
+	/// Sign and send each of the transactions in order. See
+	/// [`crate::WalletSolanaSignAndSendTransaction::sign_and_send_transactions_each`].
+	async fn sign_and_send_transactions_each(
+		&self,
+		inputs: Vec<SolanaSignAndSendTransactionProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(inputs, |props| self.sign_and_send_transaction(props)).await
+	}
+}
+
+#[async_trait(?Send)]
+impl<T: WalletSolanaSignAndSendTransaction> crate::WalletSolanaSignAndSendTransaction for T {
+	type Output = <T as WalletSolanaSignAndSendTransaction>::Output;
+
+	async fn sign_and_send_transaction(
+		&self,
+		props: SolanaSignAndSendTransactionProps,
+	) -> WalletResult<Self::Output> {
+		WalletSolanaSignAndSendTransaction::sign_and_send_transaction(self, props).await
+	}
+
+	async fn sign_and_send_transactions(
+		&self,
+		inputs: Vec<SolanaSignAndSendTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		WalletSolanaSignAndSendTransaction::sign_and_send_transactions(self, inputs).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletSolanaSignIn`].
+#[async_trait]
+pub trait WalletSolanaSignIn: Send + Sync {
+	type Output: SolanaSignInOutput + Send;
+
+	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output>;
+	async fn sign_in_many(&self, inputs: Vec<SolanaSignInInput>)
+	-> WalletResult<Vec<Self::Output>>;
+
+	/// Sign in with each of the inputs in order. See
+	/// [`crate::WalletSolanaSignIn::sign_in_each`].
+	async fn sign_in_each(&self, inputs: Vec<SolanaSignInInput>) -> BatchResult<Self::Output> {
+		try_batch(inputs, |input| self.sign_in(input)).await
+	}
+}
+
+#[async_trait(?Send)]
+impl<T: WalletSolanaSignIn> crate::WalletSolanaSignIn for T {
+	type Output = <T as WalletSolanaSignIn>::Output;
+
+	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
+		WalletSolanaSignIn::sign_in(self, input).await
+	}
+
+	async fn sign_in_many(
+		&self,
+		inputs: Vec<SolanaSignInInput>,
+	) -> WalletResult<Vec<Self::Output>> {
+		WalletSolanaSignIn::sign_in_many(self, inputs).await
+	}
+}
+
+/// The `Send` version of [`crate::WalletSolana`].
+pub trait WalletSolana:
+	WalletSolanaSignMessage
+	+ WalletSolanaSignTransaction
+	+ WalletSolanaSignAndSendTransaction
+	+ WalletSolanaSignIn
+	+ WalletStandard
+{
+}
+
+impl<T> WalletSolana for T where
+	T: WalletSolanaSignMessage
+		+ WalletSolanaSignTransaction
+		+ WalletSolanaSignAndSendTransaction
+		+ WalletSolanaSignIn
+		+ WalletStandard
+{
+}
@@ -6,8 +6,10 @@ use solana_transaction::versioned::VersionedTransaction;
 use typed_builder::TypedBuilder;
 
 use super::SolanaSignTransactionOptions;
+use crate::BatchResult;
 use crate::SolanaSignatureOutput;
 use crate::WalletResult;
+use crate::try_batch;
 
 pub const SOLANA_SIGN_AND_SEND_TRANSACTION: &str = "solana:signAndSendTransaction";
 
@@ -88,4 +90,14 @@ pub trait WalletSolanaSignAndSendTransaction {
 		&self,
 		inputs: Vec<SolanaSignAndSendTransactionProps>,
 	) -> WalletResult<Vec<Self::Output>>;
+
+	/// Sign and send each of the transactions in order, reporting the index of
+	/// the first failure along with the outputs which completed before it.
+	/// The transactions after a failure are never sent.
+	async fn sign_and_send_transactions_each(
+		&self,
+		inputs: Vec<SolanaSignAndSendTransactionProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(inputs, |props| self.sign_and_send_transaction(props)).await
+	}
 }
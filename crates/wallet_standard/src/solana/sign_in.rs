@@ -3,14 +3,21 @@ use std::fmt::Write;
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde::Serialize;
-use solana_pubkey::Pubkey;
 use typed_builder::TypedBuilder;
 
+use super::SignInDomain;
+use super::SignInFieldError;
+use super::SignInTimestamp;
+use super::SignInUri;
+use super::SignInVerifier;
+use super::SignInVersion;
 use super::SolanaSignMessageOutput;
+use crate::BatchResult;
 use crate::SolanaSignatureOutput;
 use crate::WalletAccountInfo;
 use crate::WalletError;
 use crate::WalletResult;
+use crate::try_batch;
 
 pub const SOLANA_SIGN_IN: &str = "solana:signIn";
 
@@ -22,6 +29,12 @@ pub trait SolanaSignInOutput: SolanaSignatureOutput + SolanaSignMessageOutput {
 	fn account(&self) -> Self::Account;
 }
 
+/// The input of `solana:signIn`.
+///
+/// The fields are kept as the strings sent over the wire so inputs from any
+/// wallet can be deserialized. The setters of the single value fields also
+/// accept the validated [`SignInDomain`], [`SignInUri`], [`SignInVersion`] and
+/// [`SignInTimestamp`], and the `try_` accessors parse the fields into them.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
 #[serde(rename_all = "camelCase")]
 pub struct SolanaSignInInput {
@@ -78,115 +91,90 @@ pub struct SolanaSignInInput {
 	pub resources: Option<Vec<String>>,
 }
 
-#[async_trait(?Send)]
-pub trait WalletSolanaSignIn {
-	type Output: SolanaSignInOutput;
-
-	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output>;
-	async fn sign_in_many(&self, inputs: Vec<SolanaSignInInput>)
-	-> WalletResult<Vec<Self::Output>>;
-}
-
-/// Check tha the input and output of the sign in are valid.
-pub fn verify_sign_in(
-	input: &SolanaSignInInput,
-	output: &impl SolanaSignInOutput,
-) -> WalletResult<()> {
-	let account = output.account();
-
-	let Some(input_address) = input.address.as_ref() else {
-		return Err(WalletError::WalletSignIn);
-	};
-
-	if account.address().as_str() != input_address {
-		return Err(WalletError::WalletSignIn);
+impl SolanaSignInInput {
+	/// The `domain` as an RFC 3986 authority.
+	pub fn try_domain(&self) -> Result<Option<SignInDomain>, SignInFieldError> {
+		self.domain.as_deref().map(SignInDomain::new).transpose()
 	}
 
-	verify_output_text(input, output)?;
-
-	let signature = output.try_signature()?;
-	let signed_message = output.signed_message();
-	let bytes = account.public_key();
-	let pubkey = Pubkey::try_from(bytes).map_err(|_| WalletError::WalletPublicKey)?;
-
-	if pubkey.to_string().as_str() != input_address {
-		return Err(WalletError::WalletSignIn);
+	/// The `uri` as an RFC 3986 URI.
+	pub fn try_uri(&self) -> Result<Option<SignInUri>, SignInFieldError> {
+		self.uri.as_deref().map(SignInUri::new).transpose()
 	}
 
-	if signature.verify(&pubkey.to_bytes(), &signed_message) {
-		Ok(())
-	} else {
-		Err(WalletError::WalletSignIn)
+	/// The `version`, which must be `"1"`.
+	pub fn try_version(&self) -> Result<Option<SignInVersion>, SignInFieldError> {
+		self.version.as_deref().map(str::parse).transpose()
 	}
-}
 
-#[allow(unused_assignments)]
-fn verify_output_text(
-	input: &SolanaSignInInput,
-	output: &impl SolanaSignInOutput,
-) -> WalletResult<()> {
-	let input_text = create_sign_in_message_text(input)?;
-	let output_text = String::from_utf8(output.signed_message())
-		.map_err(|_| WalletError::ParseString("could not parse the signed_message".into()))?;
-
-	if input_text == output_text {
-		return Ok(());
+	/// The `issued_at` as an RFC 3339 timestamp.
+	pub fn try_issued_at(&self) -> Result<Option<SignInTimestamp>, SignInFieldError> {
+		self.issued_at
+			.as_deref()
+			.map(SignInTimestamp::new)
+			.transpose()
 	}
 
-	let domain = input
-		.domain
-		.as_ref()
-		.ok_or(WalletError::WalletSignInFields("Domain is required".into()))?;
-	let address = input
-		.address
-		.as_ref()
-		.ok_or(WalletError::WalletSignInFields(
-			"Address is required".into(),
-		))?;
+	/// The `expiration_time` as an RFC 3339 timestamp.
+	pub fn try_expiration_time(&self) -> Result<Option<SignInTimestamp>, SignInFieldError> {
+		self.expiration_time
+			.as_deref()
+			.map(SignInTimestamp::new)
+			.transpose()
+	}
 
-	let Some((_, output_text)) = output_text
-		.split_once(format!("{domain} wants you to sign in with your Solana account:").as_str())
-	else {
-		return Err(WalletError::WalletSignIn);
-	};
+	/// The `not_before` as an RFC 3339 timestamp.
+	pub fn try_not_before(&self) -> Result<Option<SignInTimestamp>, SignInFieldError> {
+		self.not_before
+			.as_deref()
+			.map(SignInTimestamp::new)
+			.transpose()
+	}
 
-	let Some((_, mut output_text)) = output_text.split_once(address) else {
-		return Err(WalletError::WalletSignIn);
-	};
+	/// The `resources` as RFC 3986 URIs.
+	pub fn try_resources(&self) -> Result<Option<Vec<SignInUri>>, SignInFieldError> {
+		self.resources
+			.as_ref()
+			.map(|resources| resources.iter().map(SignInUri::new).collect())
+			.transpose()
+	}
 
-	if let Some(ref statement) = input.statement {
-		let Some((_, remaining)) = output_text.split_once(statement) else {
-			return Err(WalletError::WalletSignIn);
-		};
+	/// Check that every field which has a typed alternative is valid.
+	pub fn validate(&self) -> Result<(), SignInFieldError> {
+		self.try_domain()?;
+		self.try_uri()?;
+		self.try_version()?;
+		self.try_issued_at()?;
+		self.try_expiration_time()?;
+		self.try_not_before()?;
+		self.try_resources()?;
 
-		output_text = remaining;
+		Ok(())
 	}
+}
 
-	// Use macros for concise field appending
-	macro_rules! confirm_field_exists {
-		($field:expr, $name:literal) => {
-			if let Some(ref value) = $field {
-				let Some((_, remaining)) =
-					output_text.split_once(format!("{}: {}", $name, value).as_str())
-				else {
-					return Err(WalletError::WalletSignIn);
-				};
+#[async_trait(?Send)]
+pub trait WalletSolanaSignIn {
+	type Output: SolanaSignInOutput;
 
-				output_text = remaining;
-			}
-		};
-	}
+	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output>;
+	async fn sign_in_many(&self, inputs: Vec<SolanaSignInInput>)
+	-> WalletResult<Vec<Self::Output>>;
 
-	confirm_field_exists!(input.uri, "URI");
-	confirm_field_exists!(input.version, "Version");
-	confirm_field_exists!(input.chain_id, "Chain ID");
-	confirm_field_exists!(input.nonce, "Nonce");
-	confirm_field_exists!(input.issued_at, "Issued At");
-	confirm_field_exists!(input.expiration_time, "Expiration Time");
-	confirm_field_exists!(input.not_before, "Not Before");
-	confirm_field_exists!(input.request_id, "Request ID");
+	/// Sign in with each of the `inputs` in order, reporting the index of the
+	/// first failure along with the outputs which completed before it.
+	async fn sign_in_each(&self, inputs: Vec<SolanaSignInInput>) -> BatchResult<Self::Output> {
+		try_batch(inputs, |input| self.sign_in(input)).await
+	}
+}
 
-	// TODO check resources
+/// Check that the output of the sign in is valid for the input, using the
+/// default [`SignInVerifier`].
+pub fn verify_sign_in(
+	input: &SolanaSignInInput,
+	output: &impl SolanaSignInOutput,
+) -> WalletResult<()> {
+	SignInVerifier::new().verify(input, output)?;
 
 	Ok(())
 }
@@ -0,0 +1,168 @@
+use solana_signature::Signature;
+
+use super::SolanaSignInInput;
+use super::SolanaSignInOutput;
+use super::SolanaSignMessageOutput;
+use super::SolanaSignatureOutput;
+use super::WalletSolanaSignMessage;
+use super::create_sign_in_message_text;
+use crate::Wallet;
+use crate::WalletAccountInfo;
+use crate::WalletError;
+use crate::WalletResult;
+use crate::WalletStandardConnect;
+
+/// The output of signing in over `solana:signMessage`, see
+/// [`sign_in_with_sign_message`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignMessageSignInOutput<Account, Output> {
+	/// The account that was signed in.
+	pub account: Account,
+	/// The output of signing the sign in message text.
+	pub output: Output,
+}
+
+impl<Account, Output: SolanaSignatureOutput> SolanaSignatureOutput
+	for SignMessageSignInOutput<Account, Output>
+{
+	fn try_signature(&self) -> WalletResult<Signature> {
+		self.output.try_signature()
+	}
+
+	fn signature(&self) -> Signature {
+		self.output.signature()
+	}
+}
+
+impl<Account, Output: SolanaSignMessageOutput> SolanaSignMessageOutput
+	for SignMessageSignInOutput<Account, Output>
+{
+	fn signed_message(&self) -> Vec<u8> {
+		self.output.signed_message()
+	}
+
+	fn signature_type(&self) -> Option<String> {
+		self.output.signature_type()
+	}
+}
+
+impl<Account: WalletAccountInfo + Clone, Output: SolanaSignMessageOutput> SolanaSignInOutput
+	for SignMessageSignInOutput<Account, Output>
+{
+	type Account = Account;
+
+	fn account(&self) -> Self::Account {
+		self.account.clone()
+	}
+}
+
+/// Sign in with a wallet which supports `solana:signMessage` but not
+/// `solana:signIn`.
+///
+/// The input address is filled in with the connected account before the
+/// message is rendered with [`create_sign_in_message_text`] and signed, so the
+/// output passes [`verify_sign_in`](super::verify_sign_in) just like a native
+/// sign in. Unlike a wallet, this can't choose the domain, so the input must
+/// provide one.
+pub async fn sign_in_with_sign_message<W: Wallet + WalletSolanaSignMessage>(
+	wallet: &W,
+	input: SolanaSignInInput,
+) -> WalletResult<SignMessageSignInOutput<W::Account, W::Output>> {
+	let account = wallet.wallet_account().ok_or(WalletError::WalletAccount)?;
+	let address = account.address();
+
+	if let Some(requested) = input.address {
+		if requested != address {
+			return Err(WalletError::WalletAccountNotFound(requested));
+		}
+	}
+
+	let input = SolanaSignInInput {
+		address: Some(address),
+		..input
+	};
+	let message = create_sign_in_message_text(&input)?;
+	let output = wallet.sign_message_async(message).await?;
+
+	Ok(SignMessageSignInOutput { account, output })
+}
+
+/// Connect to the wallet when it has no account and then
+/// [`sign_in_with_sign_message`].
+pub async fn connect_and_sign_in_with_sign_message<
+	W: WalletStandardConnect + WalletSolanaSignMessage,
+>(
+	wallet: &mut W,
+	input: SolanaSignInInput,
+) -> WalletResult<SignMessageSignInOutput<W::Account, W::Output>> {
+	if wallet.wallet_account().is_none() {
+		wallet.connect().await?;
+	}
+
+	sign_in_with_sign_message(wallet, input).await
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+	use solana_keypair::Keypair;
+	use solana_signer::Signer;
+
+	use super::*;
+	use crate::KeypairWallet;
+	use crate::SignInDomain;
+	use crate::verify_sign_in;
+
+	#[test]
+	fn fallback_output_passes_verification() -> WalletResult<()> {
+		let keypair = Keypair::new();
+		let address = keypair.pubkey().to_string();
+		let mut wallet = KeypairWallet::new(keypair);
+		let input = SolanaSignInInput::builder()
+			.domain(SignInDomain::new("example.com")?)
+			.statement("Sign in to the example app")
+			.nonce("abcdef12")
+			.build();
+		let output = block_on(connect_and_sign_in_with_sign_message(
+			&mut wallet,
+			input.clone(),
+		))?;
+
+		assert_eq!(output.account().address(), address);
+		verify_sign_in(&input, &output)?;
+		verify_sign_in(
+			&SolanaSignInInput {
+				address: Some(address),
+				..input
+			},
+			&output,
+		)
+	}
+
+	#[test]
+	fn fallback_requires_the_connected_account() -> WalletResult<()> {
+		let mut wallet = KeypairWallet::new(Keypair::new());
+		let input = SolanaSignInInput::builder()
+			.domain(SignInDomain::new("example.com")?)
+			.build();
+
+		assert_eq!(
+			block_on(sign_in_with_sign_message(&wallet, input.clone())),
+			Err(WalletError::WalletAccount)
+		);
+
+		let other = Keypair::new().pubkey().to_string();
+		assert_eq!(
+			block_on(connect_and_sign_in_with_sign_message(
+				&mut wallet,
+				SolanaSignInInput {
+					address: Some(other.clone()),
+					..input
+				},
+			)),
+			Err(WalletError::WalletAccountNotFound(other))
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,373 @@
+use std::fmt;
+use std::str::FromStr;
+
+use fluent_uri::Uri;
+use fluent_uri::UriRef;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+use crate::WalletError;
+
+/// The reasons a field of a sign in message is invalid.
+#[derive(Debug, Clone, thiserror::Error, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SignInFieldError {
+	#[error("`{0}` is not an RFC 3986 authority")]
+	InvalidDomain(String),
+	#[error("`{0}` is not an RFC 3986 URI")]
+	InvalidUri(String),
+	#[error("`{0}` is not an RFC 3339 timestamp")]
+	InvalidTimestamp(String),
+	#[error("`{0}` is not a supported sign in version")]
+	UnsupportedVersion(String),
+}
+
+impl From<SignInFieldError> for WalletError {
+	fn from(value: SignInFieldError) -> Self {
+		WalletError::WalletSignInFields(value.to_string())
+	}
+}
+
+/// Implement the conversions shared by the text fields, which keep the text
+/// they were parsed from so messages render exactly as they were signed.
+macro_rules! text_field {
+	($name:ident) => {
+		impl $name {
+			pub fn as_str(&self) -> &str {
+				self.as_ref()
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = SignInFieldError;
+
+			fn from_str(value: &str) -> Result<Self, Self::Err> {
+				Self::new(value)
+			}
+		}
+
+		impl TryFrom<&str> for $name {
+			type Error = SignInFieldError;
+
+			fn try_from(value: &str) -> Result<Self, Self::Error> {
+				Self::new(value)
+			}
+		}
+
+		impl TryFrom<String> for $name {
+			type Error = SignInFieldError;
+
+			fn try_from(value: String) -> Result<Self, Self::Error> {
+				Self::new(value)
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(value: $name) -> Self {
+				value.as_str().to_string()
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				f.write_str(self.as_str())
+			}
+		}
+
+		impl Serialize for $name {
+			fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serializer.serialize_str(self.as_str())
+			}
+		}
+
+		impl<'de> Deserialize<'de> for $name {
+			fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				let value = String::deserialize(deserializer)?;
+				Self::new(value).map_err(serde::de::Error::custom)
+			}
+		}
+	};
+}
+
+/// The RFC 3986 authority requesting the sign in, e.g. `example.com:3000`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SignInDomain(String);
+
+impl SignInDomain {
+	pub fn new(domain: impl Into<String>) -> Result<Self, SignInFieldError> {
+		let domain = domain.into();
+		let reference = format!("//{domain}");
+		let valid = UriRef::parse(reference.as_str()).is_ok_and(|uri| {
+			uri.authority()
+				.is_some_and(|authority| authority.as_str() == domain && !domain.is_empty())
+				&& uri.path().is_empty()
+				&& !uri.has_query()
+				&& !uri.has_fragment()
+		});
+
+		if valid {
+			Ok(Self(domain))
+		} else {
+			Err(SignInFieldError::InvalidDomain(domain))
+		}
+	}
+}
+
+impl AsRef<str> for SignInDomain {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+text_field!(SignInDomain);
+
+/// An RFC 3986 URI, used for the `uri` and `resources` of a sign in.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SignInUri(String);
+
+impl SignInUri {
+	pub fn new(uri: impl Into<String>) -> Result<Self, SignInFieldError> {
+		let uri = uri.into();
+
+		if Uri::parse(uri.as_str()).is_ok() {
+			Ok(Self(uri))
+		} else {
+			Err(SignInFieldError::InvalidUri(uri))
+		}
+	}
+}
+
+impl AsRef<str> for SignInUri {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+text_field!(SignInUri);
+
+/// An RFC 3339 timestamp, e.g. `2024-01-01T00:00:00Z`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SignInTimestamp {
+	text: String,
+	time: OffsetDateTime,
+}
+
+impl SignInTimestamp {
+	pub fn new(timestamp: impl Into<String>) -> Result<Self, SignInFieldError> {
+		let text = timestamp.into();
+
+		match OffsetDateTime::parse(&text, &Rfc3339) {
+			Ok(time) => Ok(Self { text, time }),
+			Err(_) => Err(SignInFieldError::InvalidTimestamp(text)),
+		}
+	}
+
+	/// The timestamp of the `time`. Fails when the year has more than four
+	/// digits or the offset has seconds, which RFC 3339 can't represent.
+	pub fn from_time(time: OffsetDateTime) -> Result<Self, SignInFieldError> {
+		let text = time
+			.format(&Rfc3339)
+			.map_err(|_| SignInFieldError::InvalidTimestamp(time.to_string()))?;
+
+		Ok(Self { text, time })
+	}
+
+	pub fn time(&self) -> OffsetDateTime {
+		self.time
+	}
+}
+
+impl AsRef<str> for SignInTimestamp {
+	fn as_ref(&self) -> &str {
+		&self.text
+	}
+}
+
+impl TryFrom<OffsetDateTime> for SignInTimestamp {
+	type Error = SignInFieldError;
+
+	fn try_from(value: OffsetDateTime) -> Result<Self, Self::Error> {
+		Self::from_time(value)
+	}
+}
+
+text_field!(SignInTimestamp);
+
+/// The version of the sign in message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignInVersion {
+	#[default]
+	#[serde(rename = "1")]
+	V1,
+}
+
+impl SignInVersion {
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::V1 => "1",
+		}
+	}
+}
+
+impl FromStr for SignInVersion {
+	type Err = SignInFieldError;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"1" => Ok(Self::V1),
+			_ => Err(SignInFieldError::UnsupportedVersion(value.into())),
+		}
+	}
+}
+
+impl fmt::Display for SignInVersion {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl From<SignInVersion> for String {
+	fn from(value: SignInVersion) -> Self {
+		value.as_str().to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use time::macros::datetime;
+
+	use super::*;
+	use crate::SolanaSignInInput;
+
+	#[test]
+	fn domains_are_authorities() {
+		for domain in [
+			"example.com",
+			"localhost:3000",
+			"[::1]:8080",
+			"user@example.com",
+		] {
+			assert_eq!(
+				SignInDomain::new(domain).map(String::from),
+				Ok(domain.into())
+			);
+		}
+
+		for domain in [
+			"",
+			"example.com/path",
+			"https://example.com",
+			"exa mple.com",
+			"a?b",
+		] {
+			assert_eq!(
+				SignInDomain::new(domain),
+				Err(SignInFieldError::InvalidDomain(domain.into()))
+			);
+		}
+	}
+
+	#[test]
+	fn uris_must_be_absolute() {
+		for uri in [
+			"https://example.com/login?a=1",
+			"ipfs://bafy/",
+			"urn:example:1",
+		] {
+			assert_eq!(SignInUri::new(uri).map(String::from), Ok(uri.into()));
+		}
+
+		for uri in ["", "/login", "https://exa mple.com"] {
+			assert_eq!(
+				SignInUri::new(uri),
+				Err(SignInFieldError::InvalidUri(uri.into()))
+			);
+		}
+	}
+
+	#[test]
+	fn timestamps_keep_their_text() -> Result<(), SignInFieldError> {
+		let timestamp = SignInTimestamp::new("2024-01-01T01:00:00.000+01:00")?;
+
+		assert_eq!(timestamp.time(), datetime!(2024-01-01 00:00 UTC));
+		assert_eq!(timestamp.as_str(), "2024-01-01T01:00:00.000+01:00");
+		assert_eq!(
+			SignInTimestamp::from_time(datetime!(2024-01-01 00:00 UTC))?.as_str(),
+			"2024-01-01T00:00:00Z"
+		);
+		assert_eq!(
+			SignInTimestamp::new("yesterday"),
+			Err(SignInFieldError::InvalidTimestamp("yesterday".into()))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn fields_serialize_to_their_text() -> Result<(), Box<dyn std::error::Error>> {
+		let timestamp = SignInTimestamp::new("2024-01-01T00:00:00Z")?;
+
+		assert_eq!(
+			serde_json::to_string(&timestamp)?,
+			r#""2024-01-01T00:00:00Z""#
+		);
+		assert_eq!(serde_json::to_string(&SignInVersion::V1)?, r#""1""#);
+		assert_eq!(
+			serde_json::from_str::<SignInVersion>(r#""1""#)?,
+			SignInVersion::V1
+		);
+		assert!(serde_json::from_str::<SignInVersion>(r#""2""#).is_err());
+		assert!(serde_json::from_str::<SignInUri>(r#""/relative""#).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn inputs_accept_strings_and_typed_values() -> Result<(), Box<dyn std::error::Error>> {
+		let typed = SolanaSignInInput::builder()
+			.domain(SignInDomain::new("example.com")?)
+			.uri(SignInUri::new("https://example.com/login")?)
+			.version(SignInVersion::V1)
+			.issued_at(SignInTimestamp::new("2024-01-01T00:00:00Z")?)
+			.resources(vec!["https://example.com/a".into()])
+			.build();
+		let text = SolanaSignInInput::builder()
+			.domain("example.com")
+			.uri("https://example.com/login")
+			.version("1")
+			.issued_at("2024-01-01T00:00:00Z")
+			.resources(vec!["https://example.com/a".into()])
+			.build();
+
+		assert_eq!(typed, text);
+		text.validate()?;
+		assert_eq!(
+			text.try_issued_at()?.map(|issued_at| issued_at.time()),
+			Some(datetime!(2024-01-01 00:00 UTC))
+		);
+		assert_eq!(
+			text.try_resources()?,
+			Some(vec![SignInUri::new("https://example.com/a")?])
+		);
+
+		let loose: SolanaSignInInput =
+			serde_json::from_str(r#"{"domain":"example.com/path","version":"2"}"#)?;
+		assert_eq!(
+			loose.try_domain(),
+			Err(SignInFieldError::InvalidDomain("example.com/path".into()))
+		);
+		assert_eq!(
+			loose.validate(),
+			Err(SignInFieldError::InvalidDomain("example.com/path".into()))
+		);
+		assert_eq!(
+			loose.try_version(),
+			Err(SignInFieldError::UnsupportedVersion("2".into()))
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,410 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::SignInDomain;
+use super::SignInFieldError;
+use super::SignInTimestamp;
+use super::SignInUri;
+use super::SignInVersion;
+use super::SolanaSignInInput;
+
+/// The end of the first line of a sign in message, after the domain.
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+/// The line which starts the list of resources.
+const RESOURCES: &str = "Resources:";
+/// The prefix of each resource in the list of resources.
+const RESOURCE_PREFIX: &str = "- ";
+/// The fields which can follow the statement, in the order they must appear.
+const FIELDS: [&str; 9] = [
+	"URI",
+	"Version",
+	"Chain ID",
+	"Nonce",
+	"Issued At",
+	"Expiration Time",
+	"Not Before",
+	"Request ID",
+	"Resources",
+];
+
+/// The reasons a sign in message can't be parsed.
+///
+/// Lines are numbered from `1`.
+#[derive(Debug, Clone, thiserror::Error, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SignInMessageError {
+	#[error("The sign in message is not valid UTF-8")]
+	InvalidUtf8,
+	#[error("Line {line} of the sign in message should be {expected}")]
+	MalformedLine { line: usize, expected: String },
+	#[error("Line {line} of the sign in message has the unknown field `{field}`")]
+	UnknownField { line: usize, field: String },
+	#[error("Line {line} of the sign in message repeats the field `{field}`")]
+	DuplicateField { line: usize, field: String },
+	#[error("Line {line} of the sign in message has the field `{field}` after `{previous}`")]
+	FieldOrder {
+		line: usize,
+		field: String,
+		previous: String,
+	},
+	#[error("Line {line} of the sign in message has an invalid value: {error}")]
+	InvalidValue {
+		line: usize,
+		error: SignInFieldError,
+	},
+}
+
+impl SignInMessageError {
+	fn malformed(line: usize, expected: &str) -> Self {
+		Self::MalformedLine {
+			line,
+			expected: expected.into(),
+		}
+	}
+
+	fn invalid(line: usize) -> impl FnOnce(SignInFieldError) -> Self {
+		move |error| Self::InvalidValue { line, error }
+	}
+}
+
+/// Parse the `signed_message` of a
+/// [`SolanaSignInOutput`](super::SolanaSignInOutput) into the input which
+/// produced it.
+///
+/// The parser is strict: the message must have exactly the layout written by
+/// [`create_sign_in_message_text`](super::create_sign_in_message_text), so
+/// rendering the parsed input gives back the same message.
+pub fn parse_sign_in_message(message: &[u8]) -> Result<SolanaSignInInput, SignInMessageError> {
+	std::str::from_utf8(message)
+		.map_err(|_| SignInMessageError::InvalidUtf8)?
+		.parse()
+}
+
+impl FromStr for SolanaSignInInput {
+	type Err = SignInMessageError;
+
+	/// Parse the text of a sign in message. See [`parse_sign_in_message`].
+	///
+	/// A message with a single section after the address, e.g. `URI: …`, is
+	/// read as fields when it starts with a known field and as the statement
+	/// otherwise. Both readings render to the same message.
+	fn from_str(message: &str) -> Result<Self, Self::Err> {
+		let mut input = SolanaSignInInput::builder().build();
+		let (header, rest) = message.split_once('\n').unwrap_or((message, ""));
+
+		let domain = header
+			.strip_suffix(HEADER_SUFFIX)
+			.filter(|domain| !domain.is_empty())
+			.ok_or_else(|| {
+				SignInMessageError::malformed(
+					1,
+					"`<domain> wants you to sign in with your Solana account:`",
+				)
+			})?;
+		input.domain = Some(
+			SignInDomain::new(domain)
+				.map_err(SignInMessageError::invalid(1))?
+				.into(),
+		);
+
+		let (address, rest) = match rest.split_once('\n') {
+			Some((address, rest)) => (address, Some(rest)),
+			None => (rest, None),
+		};
+
+		if address.is_empty() {
+			return Err(SignInMessageError::malformed(2, "the address"));
+		}
+
+		input.address = Some(address.into());
+
+		let Some(rest) = rest else {
+			return Ok(input);
+		};
+
+		let Some(body) = rest.strip_prefix('\n') else {
+			return Err(SignInMessageError::malformed(3, "an empty line"));
+		};
+
+		let (statement, fields) = match body.split_once("\n\n") {
+			Some((statement, fields)) => (Some(statement), Some(fields)),
+			None if is_field_line(body.split('\n').next().unwrap_or_default()) => {
+				(None, Some(body))
+			}
+			None => (Some(body), None),
+		};
+
+		if let Some(statement) = statement {
+			if statement.contains('\n') {
+				return Err(SignInMessageError::malformed(5, "an empty line"));
+			}
+
+			input.statement = Some(statement.into());
+		}
+
+		if let Some(fields) = fields {
+			let first_line = if statement.is_some() { 6 } else { 4 };
+			parse_fields(&mut input, fields, first_line)?;
+		}
+
+		Ok(input)
+	}
+}
+
+/// Whether the `line` starts one of the [`FIELDS`].
+fn is_field_line(line: &str) -> bool {
+	line == RESOURCES
+		|| line
+			.split_once(": ")
+			.is_some_and(|(name, _)| FIELDS.contains(&name))
+}
+
+/// Parse the `fields` of the message into the `input`, where the fields start
+/// on line `first_line` of the message.
+fn parse_fields(
+	input: &mut SolanaSignInInput,
+	fields: &str,
+	first_line: usize,
+) -> Result<(), SignInMessageError> {
+	let mut previous: Option<usize> = None;
+
+	for (offset, line) in fields.split('\n').enumerate() {
+		let number = first_line + offset;
+
+		if let Some(resources) = input.resources.as_mut() {
+			if let Some(resource) = line.strip_prefix(RESOURCE_PREFIX) {
+				resources.push(
+					SignInUri::new(resource)
+						.map_err(SignInMessageError::invalid(number))?
+						.into(),
+				);
+				continue;
+			}
+
+			if !is_field_line(line) {
+				return Err(SignInMessageError::malformed(number, "`- <resource>`"));
+			}
+		}
+
+		let (name, value) = if line == RESOURCES {
+			("Resources", None)
+		} else {
+			let (name, value) = line
+				.split_once(": ")
+				.ok_or_else(|| SignInMessageError::malformed(number, "`<field>: <value>`"))?;
+
+			(name, Some(value))
+		};
+
+		let index = FIELDS
+			.iter()
+			.position(|field| *field == name)
+			.ok_or_else(|| {
+				SignInMessageError::UnknownField {
+					line: number,
+					field: name.into(),
+				}
+			})?;
+
+		if let Some(previous) = previous {
+			if index == previous {
+				return Err(SignInMessageError::DuplicateField {
+					line: number,
+					field: name.into(),
+				});
+			}
+
+			if index < previous {
+				return Err(SignInMessageError::FieldOrder {
+					line: number,
+					field: name.into(),
+					previous: FIELDS[previous].into(),
+				});
+			}
+		}
+
+		previous = Some(index);
+
+		let Some(value) = value else {
+			input.resources = Some(vec![]);
+			continue;
+		};
+
+		let invalid = SignInMessageError::invalid(number);
+
+		match index {
+			0 => input.uri = Some(SignInUri::new(value).map_err(invalid)?.into()),
+			1 => input.version = Some(value.parse::<SignInVersion>().map_err(invalid)?.into()),
+			2 => input.chain_id = Some(value.into()),
+			3 => input.nonce = Some(value.into()),
+			4 => input.issued_at = Some(SignInTimestamp::new(value).map_err(invalid)?.into()),
+			5 => input.expiration_time = Some(SignInTimestamp::new(value).map_err(invalid)?.into()),
+			6 => input.not_before = Some(SignInTimestamp::new(value).map_err(invalid)?.into()),
+			7 => input.request_id = Some(value.into()),
+			_ => return Err(SignInMessageError::malformed(number, "`Resources:`")),
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::WalletResult;
+	use crate::create_sign_in_message_text;
+
+	fn full_input() -> WalletResult<SolanaSignInInput> {
+		Ok(SolanaSignInInput::builder()
+			.domain(SignInDomain::new("example.com")?)
+			.address("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+			.statement("Sign in to Example")
+			.uri(SignInUri::new("https://example.com/login")?)
+			.version(SignInVersion::V1)
+			.chain_id("mainnet")
+			.nonce("32891756")
+			.issued_at(SignInTimestamp::new("2024-01-01T00:00:00.000Z")?)
+			.expiration_time(SignInTimestamp::new("2024-01-02T00:00:00Z")?)
+			.not_before(SignInTimestamp::new("2024-01-01T00:00:00+00:00")?)
+			.request_id("request-1")
+			.resources(vec![
+				"ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/".into(),
+				"https://example.com/my-web2-claim.json".into(),
+			])
+			.build())
+	}
+
+	#[test]
+	fn messages_round_trip() -> WalletResult<()> {
+		let full = full_input()?;
+		let mut no_statement = full.clone();
+		no_statement.statement = None;
+		let mut only_uri = no_statement.clone();
+		only_uri.version = None;
+		only_uri.chain_id = None;
+		only_uri.nonce = None;
+		only_uri.issued_at = None;
+		only_uri.expiration_time = None;
+		only_uri.not_before = None;
+		only_uri.request_id = None;
+		only_uri.resources = Some(vec![]);
+		let bare = SolanaSignInInput::builder()
+			.domain(SignInDomain::new("localhost:3000")?)
+			.address("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM")
+			.build();
+		let mut only_statement = bare.clone();
+		only_statement.statement = Some("Welcome: back".into());
+
+		for input in [full, no_statement, only_uri, bare, only_statement] {
+			let message = create_sign_in_message_text(&input)?;
+			let parsed = parse_sign_in_message(message.as_bytes())?;
+
+			assert_eq!(parsed, input);
+			assert_eq!(create_sign_in_message_text(&parsed)?, message);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn malformed_messages_are_rejected() {
+		let header = "example.com wants you to sign in with your Solana account:\naddress";
+		let cases = [
+			(
+				"example.com wants you to sign in:\naddress".to_string(),
+				SignInMessageError::malformed(
+					1,
+					"`<domain> wants you to sign in with your Solana account:`",
+				),
+			),
+			(
+				format!("{header}\nURI: https://a"),
+				SignInMessageError::malformed(3, "an empty line"),
+			),
+			(
+				format!("{header}\n\nURI: https://a\nNonce 1"),
+				SignInMessageError::malformed(5, "`<field>: <value>`"),
+			),
+			(
+				format!("{header}\n\nStatement\n\nURI: https://a\nColor: blue"),
+				SignInMessageError::UnknownField {
+					line: 7,
+					field: "Color".into(),
+				},
+			),
+			(
+				format!("{header}\n\nNonce: 1\nURI: https://a"),
+				SignInMessageError::FieldOrder {
+					line: 5,
+					field: "URI".into(),
+					previous: "Nonce".into(),
+				},
+			),
+			(
+				format!("{header}\n\nNonce: 1\nNonce: 2"),
+				SignInMessageError::DuplicateField {
+					line: 5,
+					field: "Nonce".into(),
+				},
+			),
+			(
+				format!("{header}\n\nResources:\n- https://a\nb"),
+				SignInMessageError::malformed(6, "`- <resource>`"),
+			),
+			(
+				format!("{header}\n\nResources:\n- https://a\nURI: https://b"),
+				SignInMessageError::FieldOrder {
+					line: 6,
+					field: "URI".into(),
+					previous: "Resources".into(),
+				},
+			),
+			(
+				"example.com/login wants you to sign in with your Solana account:\naddress".into(),
+				SignInMessageError::InvalidValue {
+					line: 1,
+					error: SignInFieldError::InvalidDomain("example.com/login".into()),
+				},
+			),
+			(
+				format!("{header}\n\nVersion: 2"),
+				SignInMessageError::InvalidValue {
+					line: 4,
+					error: SignInFieldError::UnsupportedVersion("2".into()),
+				},
+			),
+			(
+				format!("{header}\n\nIssued At: yesterday"),
+				SignInMessageError::InvalidValue {
+					line: 4,
+					error: SignInFieldError::InvalidTimestamp("yesterday".into()),
+				},
+			),
+			(
+				format!("{header}\n\nResources:\n- /relative"),
+				SignInMessageError::InvalidValue {
+					line: 5,
+					error: SignInFieldError::InvalidUri("/relative".into()),
+				},
+			),
+			(
+				format!("{header}\n\nURI: https://a\n"),
+				SignInMessageError::malformed(5, "`<field>: <value>`"),
+			),
+		];
+
+		for (message, error) in cases {
+			assert_eq!(
+				message.parse::<SolanaSignInInput>(),
+				Err(error),
+				"{message}"
+			);
+		}
+
+		assert_eq!(
+			parse_sign_in_message(&[0xff]),
+			Err(SignInMessageError::InvalidUtf8)
+		);
+	}
+}
@@ -0,0 +1,621 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+use solana_pubkey::Pubkey;
+use time::OffsetDateTime;
+
+use super::SignInMessageError;
+use super::SignInTimestamp;
+use super::SolanaSignInInput;
+use super::SolanaSignInOutput;
+use super::parse_sign_in_message;
+use crate::WalletAccountInfo;
+
+/// A wall clock which returns the current time.
+pub type SignInClock = Arc<dyn Fn() -> OffsetDateTime + Send + Sync>;
+
+/// The reasons a sign in output fails verification.
+#[derive(Debug, Clone, thiserror::Error, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SignInVerificationError {
+	/// The signed message isn't a valid sign in message.
+	#[error(transparent)]
+	Message(#[from] SignInMessageError),
+	/// A field of the signed message doesn't have the expected value. `None`
+	/// means the field should be absent, or is absent.
+	#[error("The sign in field `{field}` is {received:?} but {expected:?} was expected")]
+	FieldMismatch {
+		field: String,
+		expected: Option<String>,
+		received: Option<String>,
+	},
+	/// The resources of the signed message differ from the requested ones.
+	#[error("The sign in resources are {received:?} but {expected:?} were expected")]
+	ResourcesMismatch {
+		expected: Option<Vec<String>>,
+		received: Option<Vec<String>>,
+	},
+	/// The signed message was signed in with a different address than the
+	/// account returned by the wallet.
+	#[error("The sign in message is for `{message}` but the account is `{account}`")]
+	AddressMismatch { account: String, message: String },
+	/// The chain of the signed message isn't one of the allowed chains.
+	#[error("The sign in chain {0:?} is not allowed")]
+	ChainNotAllowed(Option<String>),
+	/// A time field of the signed message isn't an RFC 3339 timestamp.
+	#[error("The sign in field `{field}` is not a valid timestamp: `{value}`")]
+	InvalidTimestamp { field: String, value: String },
+	/// The message was issued longer than the `max_age` of the verifier ago,
+	/// or has no `Issued At` when a `max_age` is required.
+	#[error("The sign in message issued at {issued_at:?} is older than {max_age:?}")]
+	TooOld {
+		issued_at: Option<SignInTimestamp>,
+		max_age: Duration,
+	},
+	/// The message was issued in the future.
+	#[error("The sign in message is issued in the future at {0}")]
+	IssuedInFuture(SignInTimestamp),
+	/// The message expired.
+	#[error("The sign in message expired at {0}")]
+	Expired(SignInTimestamp),
+	/// The message isn't valid yet.
+	#[error("The sign in message is not valid before {0}")]
+	NotYetValid(SignInTimestamp),
+	/// The public key of the account is invalid.
+	#[error("The sign in account has an invalid public key")]
+	InvalidPublicKey,
+	/// The signature doesn't verify against the signed message.
+	#[error("The sign in signature is not valid")]
+	InvalidSignature,
+	/// The nonce of the signed message was never issued or was already used,
+	/// e.g. by a `siws` server.
+	#[error("The sign in nonce {0:?} was not issued or was already used")]
+	UnknownNonce(Option<String>),
+}
+
+/// Verifies the output of `solana:signIn` against the input which was
+/// requested.
+///
+/// The signed message must contain every field of the input. The wallet may
+/// only choose the domain and address when the input leaves them empty, so
+/// any other field which the input leaves empty must be absent.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use wallet_standard::SignInVerifier;
+/// use wallet_standard::SolanaChain;
+///
+/// let verifier = SignInVerifier::new()
+/// 	.domain("example.com")
+/// 	.uri("https://example.com/login")
+/// 	.chain_ids([SolanaChain::Mainnet])
+/// 	.max_age(Duration::from_secs(600))
+/// 	.clock_skew(Duration::from_secs(60));
+/// ```
+#[derive(Clone)]
+pub struct SignInVerifier {
+	domain: Option<String>,
+	uri: Option<String>,
+	chain_ids: Option<Vec<String>>,
+	max_age: Option<Duration>,
+	clock_skew: Duration,
+	clock: SignInClock,
+}
+
+impl SignInVerifier {
+	pub fn new() -> Self {
+		Self {
+			domain: None,
+			uri: None,
+			chain_ids: None,
+			max_age: None,
+			clock_skew: Duration::ZERO,
+			clock: Arc::new(OffsetDateTime::now_utc),
+		}
+	}
+
+	/// Require the signed message to be for the `domain`, even when the input
+	/// lets the wallet choose it. A domain in the input is still checked, so
+	/// both must match.
+	#[must_use]
+	pub fn domain(mut self, domain: impl Into<String>) -> Self {
+		self.domain = Some(domain.into());
+		self
+	}
+
+	/// Require the signed message to have the `uri`. A URI in the input is
+	/// still checked, so both must match.
+	#[must_use]
+	pub fn uri(mut self, uri: impl Into<String>) -> Self {
+		self.uri = Some(uri.into());
+		self
+	}
+
+	/// Require the signed message to have one of the `chain_ids`.
+	#[must_use]
+	pub fn chain_ids<C: Into<String>>(mut self, chain_ids: impl IntoIterator<Item = C>) -> Self {
+		self.chain_ids = Some(chain_ids.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Require the signed message to have been issued at most `max_age` ago.
+	/// Messages without an `Issued At` are rejected.
+	#[must_use]
+	pub fn max_age(mut self, max_age: Duration) -> Self {
+		self.max_age = Some(max_age);
+		self
+	}
+
+	/// Allow the clocks of the wallet and the verifier to differ by up to
+	/// `clock_skew` when checking the time fields. Defaults to zero.
+	#[must_use]
+	pub fn clock_skew(mut self, clock_skew: Duration) -> Self {
+		self.clock_skew = clock_skew;
+		self
+	}
+
+	/// Check the time fields with the provided clock instead of the system
+	/// clock.
+	#[must_use]
+	pub fn clock(mut self, clock: impl Fn() -> OffsetDateTime + Send + Sync + 'static) -> Self {
+		self.clock = Arc::new(clock);
+		self
+	}
+
+	/// Verify the `output` of signing in with the `input`, returning the
+	/// signed message as an input with the fields chosen by the wallet.
+	pub fn verify(
+		&self,
+		input: &SolanaSignInInput,
+		output: &impl SolanaSignInOutput,
+	) -> Result<SolanaSignInInput, SignInVerificationError> {
+		let signed_message = output.signed_message();
+		let signed = parse_sign_in_message(&signed_message)?;
+
+		self.verify_fields(input, &signed)?;
+		self.verify_times(&signed)?;
+
+		let account = output.account();
+		let address = signed.address.clone().unwrap_or_default();
+
+		if account.address() != address {
+			return Err(SignInVerificationError::AddressMismatch {
+				account: account.address(),
+				message: address,
+			});
+		}
+
+		let pubkey = Pubkey::try_from(account.public_key())
+			.map_err(|_| SignInVerificationError::InvalidPublicKey)?;
+
+		if pubkey.to_string() != address {
+			return Err(SignInVerificationError::AddressMismatch {
+				account: pubkey.to_string(),
+				message: address,
+			});
+		}
+
+		let signature = output
+			.try_signature()
+			.map_err(|_| SignInVerificationError::InvalidSignature)?;
+
+		if signature.verify(&pubkey.to_bytes(), &signed_message) {
+			Ok(signed)
+		} else {
+			Err(SignInVerificationError::InvalidSignature)
+		}
+	}
+
+	fn verify_fields(
+		&self,
+		input: &SolanaSignInInput,
+		signed: &SolanaSignInInput,
+	) -> Result<(), SignInVerificationError> {
+		// The wallet chooses the domain and address when they aren't provided.
+		let fields = [
+			(
+				"Domain",
+				input.domain.as_ref(),
+				signed.domain.as_ref(),
+				true,
+			),
+			(
+				"Address",
+				input.address.as_ref(),
+				signed.address.as_ref(),
+				true,
+			),
+			(
+				"Statement",
+				input.statement.as_ref(),
+				signed.statement.as_ref(),
+				false,
+			),
+			("URI", input.uri.as_ref(), signed.uri.as_ref(), false),
+			(
+				"Version",
+				input.version.as_ref(),
+				signed.version.as_ref(),
+				false,
+			),
+			(
+				"Chain ID",
+				input.chain_id.as_ref(),
+				signed.chain_id.as_ref(),
+				false,
+			),
+			("Nonce", input.nonce.as_ref(), signed.nonce.as_ref(), false),
+			(
+				"Issued At",
+				input.issued_at.as_ref(),
+				signed.issued_at.as_ref(),
+				false,
+			),
+			(
+				"Expiration Time",
+				input.expiration_time.as_ref(),
+				signed.expiration_time.as_ref(),
+				false,
+			),
+			(
+				"Not Before",
+				input.not_before.as_ref(),
+				signed.not_before.as_ref(),
+				false,
+			),
+			(
+				"Request ID",
+				input.request_id.as_ref(),
+				signed.request_id.as_ref(),
+				false,
+			),
+		];
+
+		for (field, expected, received, chosen_by_wallet) in fields {
+			if expected.is_none() && chosen_by_wallet {
+				continue;
+			}
+
+			if expected != received {
+				return Err(SignInVerificationError::FieldMismatch {
+					field: field.into(),
+					expected: expected.cloned(),
+					received: received.cloned(),
+				});
+			}
+		}
+
+		// The verifier may require a domain and URI on top of the input.
+		let required = [
+			("Domain", self.domain.as_ref(), signed.domain.as_ref()),
+			("URI", self.uri.as_ref(), signed.uri.as_ref()),
+		];
+
+		for (field, expected, received) in required {
+			if expected.is_some() && expected != received {
+				return Err(SignInVerificationError::FieldMismatch {
+					field: field.into(),
+					expected: expected.cloned(),
+					received: received.cloned(),
+				});
+			}
+		}
+
+		if input.resources != signed.resources {
+			return Err(SignInVerificationError::ResourcesMismatch {
+				expected: input.resources.clone(),
+				received: signed.resources.clone(),
+			});
+		}
+
+		if let Some(chain_ids) = self.chain_ids.as_ref() {
+			let allowed = signed
+				.chain_id
+				.as_ref()
+				.is_some_and(|chain_id| chain_ids.contains(chain_id));
+
+			if !allowed {
+				return Err(SignInVerificationError::ChainNotAllowed(
+					signed.chain_id.clone(),
+				));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn verify_times(&self, signed: &SolanaSignInInput) -> Result<(), SignInVerificationError> {
+		let now = (self.clock)();
+		let earliest = now - self.clock_skew;
+		let latest = now + self.clock_skew;
+
+		let issued_at = timestamp("Issued At", signed.issued_at.as_ref())?;
+
+		if let Some(issued_at) = issued_at.as_ref() {
+			if issued_at.time() > latest {
+				return Err(SignInVerificationError::IssuedInFuture(issued_at.clone()));
+			}
+		}
+
+		if let Some(max_age) = self.max_age {
+			let fresh = issued_at
+				.as_ref()
+				.is_some_and(|issued_at| issued_at.time() + max_age >= earliest);
+
+			if !fresh {
+				return Err(SignInVerificationError::TooOld { issued_at, max_age });
+			}
+		}
+
+		if let Some(expiration_time) =
+			timestamp("Expiration Time", signed.expiration_time.as_ref())?
+		{
+			if expiration_time.time() <= earliest {
+				return Err(SignInVerificationError::Expired(expiration_time));
+			}
+		}
+
+		if let Some(not_before) = timestamp("Not Before", signed.not_before.as_ref())? {
+			if not_before.time() > latest {
+				return Err(SignInVerificationError::NotYetValid(not_before));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl Default for SignInVerifier {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl std::fmt::Debug for SignInVerifier {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SignInVerifier")
+			.field("domain", &self.domain)
+			.field("uri", &self.uri)
+			.field("chain_ids", &self.chain_ids)
+			.field("max_age", &self.max_age)
+			.field("clock_skew", &self.clock_skew)
+			.finish_non_exhaustive()
+	}
+}
+
+/// Parse a time field of the signed message.
+fn timestamp(
+	field: &str,
+	value: Option<&String>,
+) -> Result<Option<SignInTimestamp>, SignInVerificationError> {
+	value
+		.map(|value| {
+			SignInTimestamp::new(value.as_str()).map_err(|_| {
+				SignInVerificationError::InvalidTimestamp {
+					field: field.into(),
+					value: value.clone(),
+				}
+			})
+		})
+		.transpose()
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+	use solana_keypair::Keypair;
+	use time::macros::datetime;
+
+	use super::*;
+	use crate::SolanaChain;
+	use crate::WalletResult;
+	use crate::WalletSolanaPubkey;
+	use crate::WalletSolanaSignIn;
+	use crate::solana::connected_wallet;
+
+	fn verifier_at(now: OffsetDateTime) -> SignInVerifier {
+		SignInVerifier::new().clock(move || now)
+	}
+
+	#[test]
+	fn wallet_chosen_address_is_accepted() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.nonce("abc")
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+		let signed = SignInVerifier::new().verify(&input, &output)?;
+
+		assert_eq!(signed.address, Some(wallet.solana_pubkey().to_string()));
+
+		Ok(())
+	}
+
+	#[test]
+	fn time_windows_allow_clock_skew() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.issued_at("2024-01-01T00:00:00Z")
+			.not_before("2024-01-01T00:00:00Z")
+			.expiration_time("2024-01-01T00:10:00Z")
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+
+		verifier_at(datetime!(2024-01-01 00:05 UTC)).verify(&input, &output)?;
+		assert_eq!(
+			verifier_at(datetime!(2024-01-01 00:10 UTC)).verify(&input, &output),
+			Err(SignInVerificationError::Expired(SignInTimestamp::new(
+				"2024-01-01T00:10:00Z"
+			)?))
+		);
+		assert_eq!(
+			verifier_at(datetime!(2023-12-31 23:59 UTC)).verify(&input, &output),
+			Err(SignInVerificationError::IssuedInFuture(
+				SignInTimestamp::new("2024-01-01T00:00:00Z")?
+			))
+		);
+		verifier_at(datetime!(2023-12-31 23:59 UTC))
+			.clock_skew(Duration::from_secs(60))
+			.verify(&input, &output)?;
+		verifier_at(datetime!(2024-01-01 00:10:30 UTC))
+			.clock_skew(Duration::from_secs(60))
+			.verify(&input, &output)?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn not_before_must_have_passed() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.not_before("2024-01-01T00:00:00Z")
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+
+		assert_eq!(
+			verifier_at(datetime!(2023-12-31 23:00 UTC)).verify(&input, &output),
+			Err(SignInVerificationError::NotYetValid(SignInTimestamp::new(
+				"2024-01-01T00:00:00Z"
+			)?))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn expected_domain_uri_and_chains_are_checked() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.uri("https://example.com/login")
+			.chain_id(SolanaChain::Devnet)
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+
+		SignInVerifier::new()
+			.domain("example.com")
+			.uri("https://example.com/login")
+			.chain_ids([SolanaChain::Devnet, SolanaChain::Testnet])
+			.verify(&input, &output)?;
+		assert_eq!(
+			SignInVerifier::new()
+				.domain("evil.com")
+				.verify(&input, &output),
+			Err(SignInVerificationError::FieldMismatch {
+				field: "Domain".into(),
+				expected: Some("evil.com".into()),
+				received: Some("example.com".into()),
+			})
+		);
+		assert_eq!(
+			SignInVerifier::new()
+				.uri("https://example.com/other")
+				.verify(&input, &output),
+			Err(SignInVerificationError::FieldMismatch {
+				field: "URI".into(),
+				expected: Some("https://example.com/other".into()),
+				received: Some("https://example.com/login".into()),
+			})
+		);
+		assert_eq!(
+			SignInVerifier::new()
+				.chain_ids([SolanaChain::Mainnet])
+				.verify(&input, &output),
+			Err(SignInVerificationError::ChainNotAllowed(Some(
+				SolanaChain::Devnet.into()
+			)))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn old_messages_fail_the_max_age() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.issued_at("2024-01-01T00:00:00Z")
+			.build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+		let max_age = Duration::from_secs(300);
+
+		verifier_at(datetime!(2024-01-01 00:05 UTC))
+			.max_age(max_age)
+			.verify(&input, &output)?;
+		assert_eq!(
+			verifier_at(datetime!(2024-01-01 00:06 UTC))
+				.max_age(max_age)
+				.verify(&input, &output),
+			Err(SignInVerificationError::TooOld {
+				issued_at: Some(SignInTimestamp::new("2024-01-01T00:00:00Z")?),
+				max_age,
+			})
+		);
+		verifier_at(datetime!(2024-01-01 00:06 UTC))
+			.max_age(max_age)
+			.clock_skew(Duration::from_secs(60))
+			.verify(&input, &output)?;
+
+		let undated = SolanaSignInInput::builder().domain("example.com").build();
+		let output = block_on(wallet.sign_in(undated.clone()))?;
+		assert_eq!(
+			verifier_at(datetime!(2024-01-01 00:00 UTC))
+				.max_age(max_age)
+				.verify(&undated, &output),
+			Err(SignInVerificationError::TooOld {
+				issued_at: None,
+				max_age,
+			})
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn verifier_and_input_domains_are_both_checked() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let input = SolanaSignInInput::builder().domain("example.com").build();
+		let output = block_on(wallet.sign_in(input.clone()))?;
+		let other = SolanaSignInInput::builder().domain("other.com").build();
+
+		assert_eq!(
+			SignInVerifier::new()
+				.domain("example.com")
+				.verify(&other, &output),
+			Err(SignInVerificationError::FieldMismatch {
+				field: "Domain".into(),
+				expected: Some("other.com".into()),
+				received: Some("example.com".into()),
+			})
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn fields_the_input_omits_must_be_absent() -> WalletResult<()> {
+		let wallet = connected_wallet(vec![Keypair::new()]);
+		let signed_input = SolanaSignInInput::builder()
+			.domain("example.com")
+			.nonce("wallet-nonce")
+			.build();
+		let output = block_on(wallet.sign_in(signed_input))?;
+		let input = SolanaSignInInput::builder().domain("example.com").build();
+
+		assert_eq!(
+			SignInVerifier::new().verify(&input, &output),
+			Err(SignInVerificationError::FieldMismatch {
+				field: "Nonce".into(),
+				expected: None,
+				received: Some("wallet-nonce".into()),
+			})
+		);
+
+		Ok(())
+	}
+}
@@ -4,7 +4,9 @@ use solana_keypair::Keypair;
 use solana_signature::Signature;
 use solana_signer::Signer;
 
+use crate::BatchResult;
 use crate::WalletResult;
+use crate::try_batch;
 
 /// Feature identifier for the Solana sign message feature.
 ///
@@ -240,6 +242,15 @@ pub trait WalletSolanaSignMessage {
 		&self,
 		messages: Vec<M>,
 	) -> WalletResult<Vec<Self::Output>>;
+
+	/// Sign each of the messages in order, reporting the index of the first
+	/// failure along with the outputs which completed before it.
+	async fn sign_messages_each<M: Into<Vec<u8>>>(
+		&self,
+		messages: Vec<M>,
+	) -> BatchResult<Self::Output> {
+		try_batch(messages, |message| self.sign_message_async(message)).await
+	}
 }
 
 /// Implementation of `WalletSolanaSignMessage` for Solana Keypair.
@@ -6,7 +6,9 @@ use solana_transaction::Transaction;
 use solana_transaction::versioned::VersionedTransaction;
 use typed_builder::TypedBuilder;
 
+use crate::BatchResult;
 use crate::WalletResult;
+use crate::try_batch;
 
 pub const SOLANA_SIGN_TRANSACTION: &str = "solana:signTransaction";
 
@@ -92,4 +94,13 @@ pub trait WalletSolanaSignTransaction {
 		&self,
 		inputs: Vec<SolanaSignTransactionProps>,
 	) -> WalletResult<Vec<Self::Output>>;
+
+	/// Sign each of the transactions in order, reporting the index of the first
+	/// failure along with the outputs which completed before it.
+	async fn sign_transactions_each(
+		&self,
+		inputs: Vec<SolanaSignTransactionProps>,
+	) -> BatchResult<Self::Output> {
+		try_batch(inputs, |props| self.sign_transaction(props)).await
+	}
 }
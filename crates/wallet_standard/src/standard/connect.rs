@@ -61,7 +61,7 @@ pub trait StandardConnectOutput {
 /// // Create a default connection request (interactive)
 /// let default_connect = StandardConnectInput::default();
 /// ```
-#[derive(Default, Debug, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(feature = "browser", wasm_bindgen::prelude::wasm_bindgen)]
 pub struct StandardConnectInput {
@@ -81,7 +81,7 @@ pub struct StandardConnectInput {
 	/// If set to `false` or not provided, the wallet may display UI to prompt
 	/// the user to authorize accounts.
 	#[builder(default, setter(into, strip_option))]
-	silent: Option<bool>,
+	pub silent: Option<bool>,
 }
 
 /// Trait for wallets that support connecting to authorize accounts.
@@ -1,7 +1,22 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+use serde::Deserialize;
+use serde::Serialize;
+use typed_builder::TypedBuilder;
+
+use crate::WalletError;
+use crate::WalletInfo;
 use crate::WalletResult;
 
 pub const STANDARD_EVENTS: &str = "standard:events";
 
+/// The name of the only event defined by `standard:events`, emitted when the
+/// properties of the wallet change.
+pub const STANDARD_EVENTS_CHANGE: &str = "change";
+
 pub trait StandardEventProperties {
 	type Features;
 	type WalletAccount;
@@ -38,3 +53,341 @@ pub trait ConnectedWalletStandardEvents {
 	/// Listen for changes to the Wallet's properties.
 	fn on(&self, event: impl AsRef<str>, callback: &Self::Callback) -> WalletResult<Box<dyn Fn()>>;
 }
+
+/// The properties of a wallet which changed, as delivered to `change`
+/// listeners.
+///
+/// Only the properties which changed are set, and they hold the **new** value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct StandardEventsChange<A> {
+	#[builder(default, setter(into, strip_option))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub chains: Option<Vec<String>>,
+	#[builder(default, setter(into, strip_option))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub features: Option<Vec<String>>,
+	#[builder(default, setter(into, strip_option))]
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub accounts: Option<Vec<A>>,
+}
+
+impl<A> StandardEventsChange<A> {
+	/// Whether no property changed.
+	pub fn is_empty(&self) -> bool {
+		self.chains.is_none() && self.features.is_none() && self.accounts.is_none()
+	}
+
+	/// The properties which differ between two versions of a wallet.
+	pub fn between<W>(previous: &W, current: &W) -> Self
+	where
+		W: WalletInfo<Account = A>,
+		A: PartialEq,
+	{
+		fn changed<T: PartialEq>(previous: &[T], current: Vec<T>) -> Option<Vec<T>> {
+			(previous != current.as_slice()).then_some(current)
+		}
+
+		Self {
+			chains: changed(&previous.chains(), current.chains()),
+			features: changed(&previous.features(), current.features()),
+			accounts: changed(&previous.accounts(), current.accounts()),
+		}
+	}
+}
+
+impl<A> Default for StandardEventsChange<A> {
+	fn default() -> Self {
+		Self {
+			chains: None,
+			features: None,
+			accounts: None,
+		}
+	}
+}
+
+impl<A: Clone> StandardEventProperties for StandardEventsChange<A> {
+	type Features = Vec<String>;
+	type WalletAccount = A;
+
+	fn chains(&self) -> Option<Vec<String>> {
+		self.chains.clone()
+	}
+
+	fn features(&self) -> Option<Self::Features> {
+		self.features.clone()
+	}
+
+	fn accounts(&self) -> Option<Vec<Self::WalletAccount>> {
+		self.accounts.clone()
+	}
+}
+
+/// A `change` listener registered with a [`WalletEventEmitter`].
+pub type StandardEventsListener<A> = Arc<dyn Fn(&StandardEventsChange<A>) + Send + Sync>;
+
+struct WalletEventEmitterInner<A> {
+	next_id: u64,
+	listeners: Vec<(u64, StandardEventsListener<A>)>,
+}
+
+/// A runtime agnostic implementation of `standard:events` for native
+/// wallets.
+///
+/// Wallet authors embed the emitter and call [`WalletEventEmitter::emit`]
+/// whenever the chains, features or accounts of the wallet change. Listeners
+/// are called synchronously, in the order they subscribed. Clones of the
+/// emitter share their listeners.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::sync::Mutex;
+///
+/// use wallet_standard::StandardEventsChange;
+/// use wallet_standard::WalletEventEmitter;
+///
+/// let emitter = WalletEventEmitter::<String>::new();
+/// let changes = Arc::new(Mutex::new(vec![]));
+/// let subscription = emitter.subscribe({
+/// 	let changes = changes.clone();
+/// 	move |change| changes.lock().unwrap().push(change.clone())
+/// });
+///
+/// let change = StandardEventsChange::builder()
+/// 	.accounts(vec!["address".to_string()])
+/// 	.build();
+/// emitter.emit(&change);
+///
+/// // Dropping the subscription removes the listener.
+/// drop(subscription);
+/// emitter.emit(&change);
+///
+/// assert_eq!(*changes.lock().unwrap(), [change]);
+/// ```
+pub struct WalletEventEmitter<A>(Arc<Mutex<WalletEventEmitterInner<A>>>);
+
+impl<A: 'static> WalletEventEmitter<A> {
+	pub fn new() -> Self {
+		Self(Arc::new(Mutex::new(WalletEventEmitterInner {
+			next_id: 0,
+			listeners: Vec::new(),
+		})))
+	}
+
+	/// Call the `listener` on every change until the returned subscription is
+	/// dropped.
+	#[must_use = "the listener is removed when the subscription is dropped"]
+	pub fn subscribe(
+		&self,
+		listener: impl Fn(&StandardEventsChange<A>) + Send + Sync + 'static,
+	) -> WalletEventSubscription {
+		let id = self.add_listener(Arc::new(listener));
+
+		WalletEventSubscription {
+			unsubscribe: Some(self.unsubscriber(id)),
+		}
+	}
+
+	/// Notify every listener of the `change`, returning the number of
+	/// listeners which were called. Empty changes are not delivered.
+	pub fn emit(&self, change: &StandardEventsChange<A>) -> usize {
+		if change.is_empty() {
+			return 0;
+		}
+
+		// Listeners are called without holding the lock, so they can subscribe
+		// and unsubscribe.
+		let listeners = self
+			.inner()
+			.listeners
+			.iter()
+			.map(|(_, listener)| listener.clone())
+			.collect::<Vec<_>>();
+
+		for listener in &listeners {
+			listener(change);
+		}
+
+		listeners.len()
+	}
+
+	/// The number of registered listeners.
+	pub fn listener_count(&self) -> usize {
+		self.inner().listeners.len()
+	}
+
+	fn inner(&self) -> std::sync::MutexGuard<'_, WalletEventEmitterInner<A>> {
+		self.0
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+	}
+
+	fn add_listener(&self, listener: StandardEventsListener<A>) -> u64 {
+		let mut inner = self.inner();
+		let id = inner.next_id;
+		inner.next_id += 1;
+		inner.listeners.push((id, listener));
+
+		id
+	}
+
+	fn unsubscriber(&self, id: u64) -> Box<dyn Fn() + Send + Sync> {
+		let inner: Weak<_> = Arc::downgrade(&self.0);
+
+		Box::new(move || {
+			if let Some(inner) = inner.upgrade() {
+				inner
+					.lock()
+					.unwrap_or_else(std::sync::PoisonError::into_inner)
+					.listeners
+					.retain(|(listener_id, _)| *listener_id != id);
+			}
+		})
+	}
+}
+
+impl<A: 'static> Default for WalletEventEmitter<A> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<A> Clone for WalletEventEmitter<A> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+
+impl<A> fmt::Debug for WalletEventEmitter<A> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let listeners = self.0.lock().map_or(0, |inner| inner.listeners.len());
+
+		f.debug_struct("WalletEventEmitter")
+			.field("listeners", &listeners)
+			.finish()
+	}
+}
+
+impl<A: 'static> ConnectedWalletStandardEvents for WalletEventEmitter<A> {
+	type Callback = StandardEventsListener<A>;
+
+	/// Only the [`STANDARD_EVENTS_CHANGE`] event is supported.
+	fn on(&self, event: impl AsRef<str>, callback: &Self::Callback) -> WalletResult<Box<dyn Fn()>> {
+		if event.as_ref() != STANDARD_EVENTS_CHANGE {
+			return Err(WalletError::InvalidArguments);
+		}
+
+		let id = self.add_listener(callback.clone());
+
+		Ok(self.unsubscriber(id))
+	}
+}
+
+/// Removes its listener from the [`WalletEventEmitter`] when dropped.
+#[must_use = "the listener is removed when the subscription is dropped"]
+pub struct WalletEventSubscription {
+	unsubscribe: Option<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl WalletEventSubscription {
+	/// Keep the listener registered for as long as the emitter exists.
+	pub fn detach(mut self) {
+		self.unsubscribe = None;
+	}
+}
+
+impl fmt::Debug for WalletEventSubscription {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("WalletEventSubscription")
+			.field("active", &self.unsubscribe.is_some())
+			.finish()
+	}
+}
+
+impl Drop for WalletEventSubscription {
+	fn drop(&mut self) {
+		if let Some(unsubscribe) = self.unsubscribe.take() {
+			unsubscribe();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::WalletInfoSnapshot;
+
+	fn wallet(chains: &[&str], accounts: &[&str]) -> WalletInfoSnapshot {
+		WalletInfoSnapshot::builder()
+			.version("1.0.0")
+			.name("Example")
+			.icon("")
+			.chains(chains.iter().map(ToString::to_string).collect())
+			.features(vec![STANDARD_EVENTS.to_string()])
+			.accounts(
+				accounts
+					.iter()
+					.map(|address| {
+						crate::WalletAccountSnapshot::builder()
+							.address(*address)
+							.public_key(vec![])
+							.build()
+					})
+					.collect(),
+			)
+			.build()
+	}
+
+	#[test]
+	fn change_only_carries_changed_properties() {
+		let previous = wallet(&["solana:devnet"], &["a"]);
+		let current = wallet(&["solana:devnet"], &["a", "b"]);
+		let change = StandardEventsChange::between(&previous, &current);
+
+		assert_eq!(change.chains, None);
+		assert_eq!(change.features, None);
+		assert_eq!(change.accounts, Some(current.accounts.clone()));
+		assert!(StandardEventsChange::between(&current, &current).is_empty());
+	}
+
+	#[test]
+	fn on_returns_a_disposer() -> WalletResult<()> {
+		let emitter = WalletEventEmitter::<String>::new();
+		let callback: StandardEventsListener<String> = Arc::new(|_| {});
+
+		assert_eq!(
+			emitter.on("unknown", &callback).err(),
+			Some(WalletError::InvalidArguments)
+		);
+
+		let dispose = emitter.on(STANDARD_EVENTS_CHANGE, &callback)?;
+		let change = StandardEventsChange::builder()
+			.chains(vec!["solana:devnet".to_string()])
+			.build();
+
+		assert_eq!(emitter.emit(&change), 1);
+		assert_eq!(emitter.emit(&StandardEventsChange::default()), 0);
+
+		dispose();
+		assert_eq!(emitter.listener_count(), 0);
+
+		Ok(())
+	}
+
+	#[test]
+	fn listeners_can_unsubscribe_while_emitting() {
+		let emitter = WalletEventEmitter::<String>::new();
+		let subscription = Arc::new(Mutex::new(None::<WalletEventSubscription>));
+		*subscription.lock().unwrap() = Some(emitter.subscribe({
+			let subscription = subscription.clone();
+			move |_| drop(subscription.lock().unwrap().take())
+		}));
+
+		let change = StandardEventsChange::builder()
+			.chains(Vec::<String>::new())
+			.build();
+
+		assert_eq!(emitter.emit(&change), 1);
+		assert_eq!(emitter.emit(&change), 0);
+	}
+}
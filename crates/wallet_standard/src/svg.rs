@@ -0,0 +1,408 @@
+use std::borrow::Cow;
+
+use quick_xml::Reader;
+use quick_xml::Writer;
+use quick_xml::escape::unescape;
+use quick_xml::events::BytesStart;
+use quick_xml::events::BytesText;
+use quick_xml::events::Event;
+
+use crate::WalletError;
+use crate::WalletIcon;
+use crate::WalletIconMimeType;
+use crate::WalletResult;
+
+/// Elements which are removed along with all of their children.
+const FORBIDDEN_ELEMENTS: [&str; 10] = [
+	"script",
+	"foreignobject",
+	"iframe",
+	"embed",
+	"object",
+	"audio",
+	"video",
+	"handler",
+	"listener",
+	"discard",
+];
+
+/// Animation elements which are removed when they target an `href` or an
+/// event handler, since they could set them to a script.
+const ANIMATION_ELEMENTS: [&str; 4] = ["set", "animate", "animatemotion", "animatetransform"];
+
+/// Schemes which can execute code when navigated to.
+const FORBIDDEN_SCHEMES: [&str; 3] = ["javascript:", "vbscript:", "data:text/html"];
+
+/// Data URLs which are safe to reference from an `href`.
+const SAFE_DATA_URLS: [&str; 4] = [
+	"data:image/png;",
+	"data:image/gif;",
+	"data:image/webp;",
+	"data:image/jpeg;",
+];
+
+/// Sanitize an svg document so that it is safe to render inline.
+///
+/// The svg is parsed and re-emitted without:
+///
+/// - `<script>`, `<foreignObject>` and other elements which can embed active
+///   content, including all of their children.
+/// - `on*` event handler attributes.
+/// - `href` and `xlink:href` attributes which aren't references to a fragment
+///   in the same document or an embedded raster image.
+/// - attributes and styles which load external resources or use `javascript:`
+///   urls.
+/// - comments, processing instructions and doctype declarations.
+///
+/// Fails with [`WalletError::InvalidIcon`] when the document can't be parsed,
+/// its root element isn't an `<svg>` or it uses entities which can't be
+/// resolved safely.
+///
+/// ```
+/// use wallet_standard::sanitize_svg;
+///
+/// let svg =
+/// 	sanitize_svg(r#"<svg onload="alert(1)"><script>alert(2)</script><rect width="1"/></svg>"#)?;
+///
+/// assert_eq!(svg, r#"<svg><rect width="1"/></svg>"#);
+/// # Ok::<(), wallet_standard::WalletError>(())
+/// ```
+pub fn sanitize_svg(svg: &str) -> WalletResult<String> {
+	let mut reader = Reader::from_str(svg);
+	let mut writer = Writer::new(Vec::new());
+	// The depth of the elements which have been written.
+	let mut depth = 0_usize;
+	// The depth within an element which is being removed.
+	let mut skip_depth = 0_usize;
+	let mut has_root = false;
+
+	loop {
+		let event = reader.read_event().map_err(|_| WalletError::InvalidIcon)?;
+
+		if skip_depth > 0 {
+			match event {
+				Event::Start(_) => skip_depth += 1,
+				Event::End(_) => skip_depth -= 1,
+				Event::Eof => return Err(WalletError::InvalidIcon),
+				_ => {}
+			}
+
+			continue;
+		}
+
+		match event {
+			Event::Start(start) => {
+				check_root(&start, depth, &mut has_root)?;
+
+				if local_name(&start) == "style" {
+					let end = start.to_end().into_owned();
+					let css = reader
+						.read_text(end.name())
+						.map_err(|_| WalletError::InvalidIcon)?;
+
+					if is_safe_css(&unescape_value(&css)?) {
+						write_event(&mut writer, Event::Start(sanitize_attributes(&start)?))?;
+						write_event(&mut writer, Event::Text(BytesText::from_escaped(css)))?;
+						write_event(&mut writer, Event::End(end))?;
+					}
+
+					continue;
+				}
+
+				if is_forbidden_element(&start)? {
+					skip_depth = 1;
+					continue;
+				}
+
+				depth += 1;
+				write_event(&mut writer, Event::Start(sanitize_attributes(&start)?))?;
+			}
+			Event::Empty(start) => {
+				check_root(&start, depth, &mut has_root)?;
+
+				if !is_forbidden_element(&start)? {
+					write_event(&mut writer, Event::Empty(sanitize_attributes(&start)?))?;
+				}
+			}
+			Event::End(end) => {
+				depth -= 1;
+				write_event(&mut writer, Event::End(end))?;
+			}
+			Event::Text(text) if depth > 0 => write_event(&mut writer, Event::Text(text))?,
+			Event::CData(data) if depth > 0 => write_event(&mut writer, Event::CData(data))?,
+			Event::GeneralRef(reference) if depth > 0 => {
+				let name = reference.decode().map_err(|_| WalletError::InvalidIcon)?;
+
+				if !reference.is_char_ref() && !is_predefined_entity(&name) {
+					return Err(WalletError::InvalidIcon);
+				}
+
+				write_event(&mut writer, Event::GeneralRef(reference))?;
+			}
+			Event::Decl(decl) if !has_root => write_event(&mut writer, Event::Decl(decl))?,
+			Event::Eof => break,
+			_ => {}
+		}
+	}
+
+	if !has_root {
+		return Err(WalletError::InvalidIcon);
+	}
+
+	String::from_utf8(writer.into_inner()).map_err(|_| WalletError::InvalidIcon)
+}
+
+impl WalletIcon {
+	/// Sanitize the icon so that it is safe to render.
+	///
+	/// Svg icons are passed through [`sanitize_svg`]. Raster icons can't
+	/// execute scripts and are returned unchanged.
+	pub fn sanitize(&self) -> WalletResult<Self> {
+		if self.mime_type() != WalletIconMimeType::Svg {
+			return Ok(self.clone());
+		}
+
+		let svg = std::str::from_utf8(self.data()).map_err(|_| WalletError::InvalidIcon)?;
+
+		Self::new(WalletIconMimeType::Svg, sanitize_svg(svg)?)
+	}
+}
+
+/// Only a single `<svg>` root element is allowed.
+fn check_root(start: &BytesStart, depth: usize, has_root: &mut bool) -> WalletResult<()> {
+	if depth > 0 {
+		return Ok(());
+	}
+
+	if *has_root || local_name(start) != "svg" {
+		return Err(WalletError::InvalidIcon);
+	}
+
+	*has_root = true;
+
+	Ok(())
+}
+
+fn is_forbidden_element(start: &BytesStart) -> WalletResult<bool> {
+	let name = local_name(start);
+
+	if FORBIDDEN_ELEMENTS.contains(&name.as_str()) {
+		return Ok(true);
+	}
+
+	if !ANIMATION_ELEMENTS.contains(&name.as_str()) {
+		return Ok(false);
+	}
+
+	for attribute in start.attributes() {
+		let attribute = attribute.map_err(|_| WalletError::InvalidIcon)?;
+		let key = attribute_local_name(attribute.key.as_ref());
+
+		if key == "attributename" {
+			let target = normalize(
+				&attribute
+					.unescape_value()
+					.map_err(|_| WalletError::InvalidIcon)?,
+			);
+			let target = target.rsplit(':').next().unwrap_or_default();
+
+			if target == "href" || target.starts_with("on") {
+				return Ok(true);
+			}
+		}
+	}
+
+	Ok(false)
+}
+
+/// Copy the element without any unsafe attributes. Attribute values are
+/// unescaped and re-escaped so that the output is always well formed.
+fn sanitize_attributes(start: &BytesStart) -> WalletResult<BytesStart<'static>> {
+	let mut sanitized =
+		BytesStart::new(String::from_utf8_lossy(start.name().as_ref()).into_owned());
+
+	for attribute in start.attributes() {
+		let attribute = attribute.map_err(|_| WalletError::InvalidIcon)?;
+		let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+		let value = attribute
+			.unescape_value()
+			.map_err(|_| WalletError::InvalidIcon)?;
+
+		if is_safe_attribute(&key, &value) {
+			sanitized.push_attribute((key.as_str(), value.as_ref()));
+		}
+	}
+
+	Ok(sanitized)
+}
+
+fn is_safe_attribute(key: &str, value: &str) -> bool {
+	let name = attribute_local_name(key.as_bytes());
+	let normalized = normalize(value);
+
+	if name.starts_with("on") {
+		return false;
+	}
+
+	if FORBIDDEN_SCHEMES
+		.iter()
+		.any(|scheme| normalized.contains(scheme))
+	{
+		return false;
+	}
+
+	if name == "href" || name == "src" {
+		return normalized.starts_with('#')
+			|| SAFE_DATA_URLS.iter().any(|url| normalized.starts_with(url));
+	}
+
+	is_safe_css(value)
+}
+
+/// Whether the css, or an attribute value, doesn't load external resources or
+/// execute code. Only `url(#fragment)` references are allowed.
+fn is_safe_css(css: &str) -> bool {
+	let normalized = normalize(css);
+
+	if normalized.contains("@import")
+		|| normalized.contains("expression(")
+		|| normalized.contains('\\')
+		|| FORBIDDEN_SCHEMES
+			.iter()
+			.any(|scheme| normalized.contains(scheme))
+	{
+		return false;
+	}
+
+	normalized.match_indices("url(").all(|(index, _)| {
+		normalized[index + 4..]
+			.trim_start_matches(['"', '\''])
+			.starts_with('#')
+	})
+}
+
+/// Lowercase the value and remove whitespace and control characters which
+/// browsers ignore in urls, e.g. `java\tscript:`.
+fn normalize(value: &str) -> String {
+	value
+		.chars()
+		.filter(|character| !character.is_whitespace() && !character.is_control())
+		.flat_map(char::to_lowercase)
+		.collect()
+}
+
+fn unescape_value(value: &str) -> WalletResult<Cow<'_, str>> {
+	unescape(value).map_err(|_| WalletError::InvalidIcon)
+}
+
+fn local_name(start: &BytesStart) -> String {
+	String::from_utf8_lossy(start.local_name().as_ref()).to_lowercase()
+}
+
+fn attribute_local_name(key: &[u8]) -> String {
+	let key = String::from_utf8_lossy(key).to_lowercase();
+
+	key.rsplit(':').next().unwrap_or_default().to_string()
+}
+
+fn is_predefined_entity(name: &str) -> bool {
+	matches!(name, "lt" | "gt" | "amp" | "apos" | "quot")
+}
+
+fn write_event(writer: &mut Writer<Vec<u8>>, event: Event) -> WalletResult<()> {
+	writer
+		.write_event(event)
+		.map_err(|_| WalletError::InvalidIcon)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn strips_active_content() -> WalletResult<()> {
+		let svg = sanitize_svg(concat!(
+			r#"<?xml version="1.0"?><!-- comment -->"#,
+			r#"<svg xmlns="http://www.w3.org/2000/svg" ONCLICK="alert(1)">"#,
+			r#"<script>alert(2)</script>"#,
+			r#"<foreignObject><div onclick="alert(3)"></div></foreignObject>"#,
+			r#"<a href="javascript:alert(4)"><rect fill="url(#grad)"/></a>"#,
+			r##"<a href="#icon"/>"##,
+			r#"<image xlink:href="https://example.com/track.png"/>"#,
+			r#"<set attributeName="href" to="javascript:alert(5)"/>"#,
+			r#"<rect style="fill: url('https://example.com/a.svg')" width="1"/>"#,
+			"</svg>"
+		))?;
+
+		assert_eq!(
+			svg,
+			concat!(
+				r#"<?xml version="1.0"?>"#,
+				r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+				r#"<a><rect fill="url(#grad)"/></a>"#,
+				r##"<a href="#icon"/>"##,
+				"<image/>",
+				r#"<rect width="1"/>"#,
+				"</svg>"
+			)
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn strips_unsafe_styles() -> WalletResult<()> {
+		let svg = sanitize_svg(concat!(
+			"<svg>",
+			"<style>rect { fill: red; }</style>",
+			"<style>@import url(https://example.com/a.css);</style>",
+			"<text>a &amp; b &#60;</text>",
+			"</svg>"
+		))?;
+
+		assert_eq!(
+			svg,
+			"<svg><style>rect { fill: red; }</style><text>a &amp; b &#60;</text></svg>"
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn attributes_are_reescaped() -> WalletResult<()> {
+		let svg = sanitize_svg(r#"<svg><text class='a"b'>&lt;</text></svg>"#)?;
+
+		assert_eq!(svg, r#"<svg><text class="a&quot;b">&lt;</text></svg>"#);
+
+		Ok(())
+	}
+
+	#[test]
+	fn rejects_unsafe_documents() {
+		for invalid in [
+			"",
+			"<html><svg/></html>",
+			"<svg/><svg/>",
+			"<svg><rect></svg>",
+			r#"<!DOCTYPE svg [<!ENTITY xss "<script>">]><svg>&xss;</svg>"#,
+		] {
+			assert_eq!(
+				sanitize_svg(invalid),
+				Err(WalletError::InvalidIcon),
+				"{invalid}"
+			);
+		}
+	}
+
+	#[test]
+	fn sanitize_icons() -> WalletResult<()> {
+		let icon = WalletIcon::new(
+			WalletIconMimeType::Svg,
+			r#"<svg onload="alert(1)"><circle r="1"/></svg>"#,
+		)?;
+
+		assert_eq!(icon.sanitize()?.data(), br#"<svg><circle r="1"/></svg>"#);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,644 @@
+//! Utilities for testing code which is generic over the wallet traits.
+//!
+//! The [`MockWallet`] wraps a [`KeypairWallet`] and implements every trait of
+//! [`crate::WalletSolana`]. Each call can be scripted to return a value, any
+//! [`WalletError`], to be delayed or to never respond, and every call is
+//! recorded with the props it was called with.
+//!
+//! ```rust
+//! use wallet_standard::prelude::*;
+//! use wallet_standard::testing::MockCall;
+//! use wallet_standard::testing::MockResponse;
+//! use wallet_standard::testing::MockWallet;
+//!
+//! # futures::executor::block_on(async {
+//! let mut wallet = MockWallet::default();
+//! let handle = wallet.handle();
+//!
+//! handle.push_connect(MockResponse::Err(WalletError::WalletWindowClosed));
+//!
+//! assert_eq!(wallet.connect().await, Err(WalletError::WalletWindowClosed));
+//! assert!(wallet.connect().await.is_ok());
+//! assert_eq!(handle.calls().len(), 2);
+//! assert!(matches!(handle.calls()[0], MockCall::Connect(_)));
+//! # });
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::future;
+use futures_timer::Delay;
+use solana_keypair::Keypair;
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::ConnectedWalletStandardEvents;
+use crate::KeypairSignInOutput;
+use crate::KeypairWallet;
+use crate::KeypairWalletAccount;
+use crate::KeypairWalletInfo;
+use crate::SolanaSignAndSendTransactionProps;
+use crate::SolanaSignInInput;
+use crate::SolanaSignTransactionProps;
+use crate::StandardConnectInput;
+use crate::StandardEventsListener;
+use crate::Wallet;
+use crate::WalletAccountInfo;
+use crate::WalletAccounts;
+use crate::WalletError;
+use crate::WalletEventStream;
+use crate::WalletResult;
+use crate::send::WalletSolanaSignAndSendTransaction;
+use crate::send::WalletSolanaSignIn;
+use crate::send::WalletSolanaSignMessage;
+use crate::send::WalletSolanaSignTransaction;
+use crate::send::WalletStandardConnect;
+use crate::send::WalletStandardDisconnect;
+
+/// The output of a `solana:signMessage` call on the [`MockWallet`].
+pub type MockSignMessageOutput = (Signature, Vec<u8>, Option<String>);
+
+/// A scripted response for a single call to the [`MockWallet`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockResponse<T> {
+	/// Delegate the call to the wrapped [`KeypairWallet`].
+	Default,
+	/// Return the provided value.
+	Ok(T),
+	/// Return the provided error.
+	Err(WalletError),
+	/// Wait for the duration before producing the inner response.
+	Delayed(Duration, Box<MockResponse<T>>),
+	/// Never respond, like a wallet whose prompt is left open.
+	Pending,
+}
+
+impl<T> MockResponse<T> {
+	/// Delay this response by the provided duration.
+	#[must_use]
+	pub fn delayed(self, duration: Duration) -> Self {
+		Self::Delayed(duration, Box::new(self))
+	}
+
+	/// Wait for any delays and return the final response.
+	async fn resolve(self) -> Self {
+		let mut response = self;
+
+		loop {
+			match response {
+				Self::Delayed(duration, inner) => {
+					Delay::new(duration).await;
+					response = *inner;
+				}
+				Self::Pending => future::pending::<()>().await,
+				response => return response,
+			}
+		}
+	}
+}
+
+impl<T> Default for MockResponse<T> {
+	fn default() -> Self {
+		Self::Default
+	}
+}
+
+impl<T> From<WalletResult<T>> for MockResponse<T> {
+	fn from(result: WalletResult<T>) -> Self {
+		match result {
+			Ok(value) => Self::Ok(value),
+			Err(error) => Self::Err(error),
+		}
+	}
+}
+
+/// A call made to the [`MockWallet`] along with the props it was called with.
+///
+/// Batch methods like `sign_messages` are recorded as one call per item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockCall {
+	Connect(StandardConnectInput),
+	Disconnect,
+	SignMessage(Vec<u8>),
+	SignTransaction(SolanaSignTransactionProps),
+	SignAndSendTransaction(SolanaSignAndSendTransactionProps),
+	SignIn(SolanaSignInInput),
+}
+
+#[derive(Debug, Default)]
+struct MockScript {
+	calls: Vec<MockCall>,
+	connect: VecDeque<MockResponse<Vec<KeypairWalletAccount>>>,
+	disconnect: VecDeque<MockResponse<()>>,
+	sign_message: VecDeque<MockResponse<MockSignMessageOutput>>,
+	sign_transaction: VecDeque<MockResponse<VersionedTransaction>>,
+	sign_and_send_transaction: VecDeque<MockResponse<Signature>>,
+	sign_in: VecDeque<MockResponse<KeypairSignInOutput>>,
+}
+
+/// A shared handle to the script and call log of a [`MockWallet`].
+///
+/// The handle can be kept by a test after the wallet has been moved into the
+/// code under test.
+#[derive(Clone, Debug, Default)]
+pub struct MockWalletHandle(Arc<Mutex<MockScript>>);
+
+macro_rules! push_response {
+	($(#[$meta:meta])* $name:ident, $field:ident, $output:ty) => {
+		$(#[$meta])*
+		pub fn $name(&self, response: impl Into<MockResponse<$output>>) -> &Self {
+			self.0.lock().unwrap().$field.push_back(response.into());
+			self
+		}
+	};
+}
+
+impl MockWalletHandle {
+	push_response!(
+		/// Script the response of the next `connect` call.
+		push_connect,
+		connect,
+		Vec<KeypairWalletAccount>
+	);
+
+	push_response!(
+		/// Script the response of the next `disconnect` call.
+		push_disconnect,
+		disconnect,
+		()
+	);
+
+	push_response!(
+		/// Script the response of the next `sign_message` call.
+		push_sign_message,
+		sign_message,
+		MockSignMessageOutput
+	);
+
+	push_response!(
+		/// Script the response of the next `sign_transaction` call.
+		push_sign_transaction,
+		sign_transaction,
+		VersionedTransaction
+	);
+
+	push_response!(
+		/// Script the response of the next `sign_and_send_transaction` call.
+		push_sign_and_send_transaction,
+		sign_and_send_transaction,
+		Signature
+	);
+
+	push_response!(
+		/// Script the response of the next `sign_in` call.
+		push_sign_in,
+		sign_in,
+		KeypairSignInOutput
+	);
+
+	/// Every call made to the wallet, in order.
+	pub fn calls(&self) -> Vec<MockCall> {
+		self.0.lock().unwrap().calls.clone()
+	}
+
+	/// Clear the recorded calls.
+	pub fn clear_calls(&self) {
+		self.0.lock().unwrap().calls.clear();
+	}
+
+	/// Remove every scripted response which hasn't been used yet.
+	pub fn clear_responses(&self) {
+		let mut script = self.0.lock().unwrap();
+		let calls = std::mem::take(&mut script.calls);
+		*script = MockScript {
+			calls,
+			..MockScript::default()
+		};
+	}
+
+	/// Record the call and take the next scripted response.
+	async fn next<T>(
+		&self,
+		call: MockCall,
+		queue: impl FnOnce(&mut MockScript) -> &mut VecDeque<MockResponse<T>>,
+	) -> MockResponse<T> {
+		let response = {
+			let mut script = self.0.lock().unwrap();
+			script.calls.push(call);
+			queue(&mut script).pop_front().unwrap_or_default()
+		};
+
+		response.resolve().await
+	}
+}
+
+/// A scriptable wallet for testing code which is generic over
+/// [`crate::WalletSolana`].
+///
+/// Calls without a scripted response are delegated to the wrapped
+/// [`KeypairWallet`], so the wallet produces valid signatures by default.
+#[derive(Debug)]
+pub struct MockWallet {
+	wallet: KeypairWallet,
+	handle: MockWalletHandle,
+}
+
+impl MockWallet {
+	/// Create a mock wallet which delegates to the provided keypair wallet.
+	pub fn new(wallet: KeypairWallet) -> Self {
+		Self {
+			wallet,
+			handle: MockWalletHandle::default(),
+		}
+	}
+
+	/// A shared handle for scripting responses and inspecting calls.
+	pub fn handle(&self) -> MockWalletHandle {
+		self.handle.clone()
+	}
+
+	/// The wrapped keypair wallet.
+	pub fn inner(&self) -> &KeypairWallet {
+		&self.wallet
+	}
+
+	/// A stream of the changes to the wrapped wallet.
+	pub fn event_stream(&self) -> WalletEventStream<KeypairWalletAccount> {
+		self.wallet.event_stream()
+	}
+}
+
+impl Default for MockWallet {
+	fn default() -> Self {
+		Self::new(KeypairWallet::new(Keypair::new()))
+	}
+}
+
+impl From<KeypairWallet> for MockWallet {
+	fn from(wallet: KeypairWallet) -> Self {
+		Self::new(wallet)
+	}
+}
+
+impl Wallet for MockWallet {
+	type Account = KeypairWalletAccount;
+	type Wallet = KeypairWalletInfo;
+
+	fn wallet(&self) -> Self::Wallet {
+		self.wallet.wallet()
+	}
+
+	fn wallet_account(&self) -> Option<Self::Account> {
+		self.wallet.wallet_account()
+	}
+}
+
+impl ConnectedWalletStandardEvents for MockWallet {
+	type Callback = StandardEventsListener<KeypairWalletAccount>;
+
+	fn on(&self, event: impl AsRef<str>, callback: &Self::Callback) -> WalletResult<Box<dyn Fn()>> {
+		self.wallet.on(event, callback)
+	}
+}
+
+impl WalletAccounts for MockWallet {
+	fn wallet_accounts(&self) -> Vec<Self::Account> {
+		self.wallet.wallet_accounts()
+	}
+
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account> {
+		self.wallet.select_account(address)
+	}
+
+	fn sync_wallet_account(&mut self) -> Option<Self::Account> {
+		self.wallet.sync_wallet_account()
+	}
+}
+
+#[async_trait]
+impl WalletStandardConnect for MockWallet {
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>> {
+		self.connect_with_options(StandardConnectInput::default())
+			.await
+	}
+
+	/// A scripted `Ok` response also connects the wrapped wallet with the same
+	/// options and selects the first scripted account, so that subsequent calls
+	/// use the accounts which were returned. Every scripted account must belong
+	/// to a keypair of the wrapped wallet, otherwise the call fails with
+	/// [`WalletError::WalletAccountNotFound`]. An empty list leaves the wrapped
+	/// wallet untouched.
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>> {
+		let call = MockCall::Connect(options.clone());
+
+		match self.handle.next(call, |script| &mut script.connect).await {
+			MockResponse::Ok(accounts) => {
+				let known = self.wallet.all_accounts();
+
+				if let Some(unknown) = accounts.iter().find(|account| {
+					!known
+						.iter()
+						.any(|known| known.address() == account.address())
+				}) {
+					return Err(WalletError::WalletAccountNotFound(unknown.address()));
+				}
+
+				if let Some(account) = accounts.first() {
+					self.wallet.connect_with_options(options).await?;
+					self.wallet.select_account(&account.address())?;
+				}
+
+				Ok(accounts)
+			}
+			MockResponse::Err(error) => Err(error),
+			_ => self.wallet.connect_with_options(options).await,
+		}
+	}
+}
+
+#[async_trait]
+impl WalletStandardDisconnect for MockWallet {
+	async fn disconnect(&mut self) -> WalletResult<()> {
+		match self
+			.handle
+			.next(MockCall::Disconnect, |script| &mut script.disconnect)
+			.await
+		{
+			MockResponse::Ok(()) => {
+				self.wallet.disconnect().await.ok();
+				Ok(())
+			}
+			MockResponse::Err(error) => Err(error),
+			_ => self.wallet.disconnect().await,
+		}
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignMessage for MockWallet {
+	type Output = MockSignMessageOutput;
+
+	async fn sign_message_async(
+		&self,
+		message: impl Into<Vec<u8>> + Send,
+	) -> WalletResult<Self::Output> {
+		let message: Vec<u8> = message.into();
+		let call = MockCall::SignMessage(message.clone());
+
+		match self
+			.handle
+			.next(call, |script| &mut script.sign_message)
+			.await
+		{
+			MockResponse::Ok(output) => Ok(output),
+			MockResponse::Err(error) => Err(error),
+			_ => self.wallet.sign_message_async(message).await,
+		}
+	}
+
+	async fn sign_messages<M: Into<Vec<u8>> + Send>(
+		&self,
+		messages: Vec<M>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(messages.len());
+
+		for message in messages {
+			outputs.push(self.sign_message_async(message).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignTransaction for MockWallet {
+	type Output = VersionedTransaction;
+
+	async fn sign_transaction(
+		&self,
+		props: SolanaSignTransactionProps,
+	) -> WalletResult<Self::Output> {
+		let call = MockCall::SignTransaction(props.clone());
+
+		match self
+			.handle
+			.next(call, |script| &mut script.sign_transaction)
+			.await
+		{
+			MockResponse::Ok(output) => Ok(output),
+			MockResponse::Err(error) => Err(error),
+			_ => self.wallet.sign_transaction(props).await,
+		}
+	}
+
+	async fn sign_transactions(
+		&self,
+		inputs: Vec<SolanaSignTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for props in inputs {
+			outputs.push(self.sign_transaction(props).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignAndSendTransaction for MockWallet {
+	type Output = Signature;
+
+	async fn sign_and_send_transaction(
+		&self,
+		props: SolanaSignAndSendTransactionProps,
+	) -> WalletResult<Self::Output> {
+		let call = MockCall::SignAndSendTransaction(props.clone());
+
+		match self
+			.handle
+			.next(call, |script| &mut script.sign_and_send_transaction)
+			.await
+		{
+			MockResponse::Ok(output) => Ok(output),
+			MockResponse::Err(error) => Err(error),
+			_ => self.wallet.sign_and_send_transaction(props).await,
+		}
+	}
+
+	async fn sign_and_send_transactions(
+		&self,
+		inputs: Vec<SolanaSignAndSendTransactionProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for props in inputs {
+			outputs.push(self.sign_and_send_transaction(props).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[async_trait]
+impl WalletSolanaSignIn for MockWallet {
+	type Output = KeypairSignInOutput;
+
+	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
+		let call = MockCall::SignIn(input.clone());
+
+		match self.handle.next(call, |script| &mut script.sign_in).await {
+			MockResponse::Ok(output) => Ok(output),
+			MockResponse::Err(error) => Err(error),
+			_ => self.wallet.sign_in(input).await,
+		}
+	}
+
+	async fn sign_in_many(
+		&self,
+		inputs: Vec<SolanaSignInInput>,
+	) -> WalletResult<Vec<Self::Output>> {
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for input in inputs {
+			outputs.push(self.sign_in(input).await?);
+		}
+
+		Ok(outputs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Instant;
+
+	use futures::executor::block_on;
+
+	use super::*;
+
+	#[test]
+	fn records_calls_with_props() -> WalletResult<()> {
+		let mut wallet = MockWallet::default();
+		let handle = wallet.handle();
+
+		block_on(async {
+			wallet.connect().await?;
+			wallet.sign_messages(vec!["one", "two"]).await?;
+			wallet.disconnect().await
+		})?;
+
+		assert_eq!(
+			handle.calls(),
+			vec![
+				MockCall::Connect(StandardConnectInput::default()),
+				MockCall::SignMessage(b"one".to_vec()),
+				MockCall::SignMessage(b"two".to_vec()),
+				MockCall::Disconnect,
+			]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn scripted_responses_are_used_in_order() -> WalletResult<()> {
+		let mut wallet = MockWallet::default();
+		let handle = wallet.handle();
+		let output = (Signature::default(), b"scripted".to_vec(), None);
+
+		handle
+			.push_sign_message(MockResponse::Ok(output.clone()))
+			.push_sign_message(MockResponse::Err(WalletError::WalletSignMessage));
+
+		block_on(async {
+			wallet.connect().await?;
+
+			assert_eq!(wallet.sign_message_async("a").await?, output);
+			assert_eq!(
+				wallet.sign_message_async("b").await,
+				Err(WalletError::WalletSignMessage)
+			);
+
+			let (_, message, _) = wallet.sign_message_async("c").await?;
+			assert_eq!(message, b"c".to_vec());
+
+			Ok(())
+		})
+	}
+
+	#[test]
+	fn failed_connect_leaves_wallet_disconnected() {
+		let mut wallet = MockWallet::default();
+		wallet
+			.handle()
+			.push_connect(MockResponse::Err(WalletError::WalletWindowBlocked));
+
+		assert_eq!(
+			block_on(wallet.connect()),
+			Err(WalletError::WalletWindowBlocked)
+		);
+		assert!(!wallet.connected());
+	}
+
+	#[test]
+	fn scripted_connect_selects_the_scripted_accounts() -> WalletResult<()> {
+		let mut wallet = MockWallet::new(
+			KeypairWallet::builder()
+				.keypairs(vec![Keypair::new(), Keypair::new()])
+				.build(),
+		);
+		let handle = wallet.handle();
+		let accounts = wallet.inner().all_accounts()[1..].to_vec();
+		let unknown = KeypairWallet::new(Keypair::new()).all_accounts();
+		let silent = StandardConnectInput::builder().silent(true).build();
+
+		handle
+			.push_connect(MockResponse::Ok(unknown.clone()))
+			.push_connect(MockResponse::Ok(accounts.clone()))
+			.push_connect(MockResponse::Ok(accounts.clone()));
+
+		assert_eq!(
+			block_on(wallet.connect()),
+			Err(WalletError::WalletAccountNotFound(unknown[0].address()))
+		);
+		assert!(!wallet.connected());
+
+		// The options are forwarded, so a silent connection can't authorize
+		// the scripted accounts.
+		assert_eq!(
+			block_on(wallet.connect_with_options(silent)),
+			Err(WalletError::WalletAccountNotFound(accounts[0].address()))
+		);
+		assert!(!wallet.connected());
+
+		assert_eq!(block_on(wallet.connect())?, accounts);
+		assert_eq!(wallet.wallet_account(), accounts.first().cloned());
+		assert!(wallet.wallet_accounts().contains(&accounts[0]));
+
+		Ok(())
+	}
+
+	#[test]
+	fn delayed_responses_wait() -> WalletResult<()> {
+		let mut wallet = MockWallet::default();
+		let delay = Duration::from_millis(20);
+		wallet
+			.handle()
+			.push_connect(MockResponse::Default.delayed(delay));
+
+		let start = Instant::now();
+		block_on(wallet.connect())?;
+
+		assert!(start.elapsed() >= delay);
+		assert!(wallet.connected());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,546 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::channel::oneshot;
+use futures::future;
+use futures::future::Either;
+use futures_timer::Delay;
+use typed_builder::TypedBuilder;
+
+use crate::ExperimentalDecryptProps;
+use crate::ExperimentalEncryptProps;
+use crate::StandardConnectInput;
+use crate::Wallet;
+use crate::WalletAccounts;
+use crate::WalletError;
+use crate::WalletExperimentalDecrypt;
+use crate::WalletExperimentalEncrypt;
+use crate::WalletResult;
+use crate::WalletStandard;
+use crate::WalletStandardConnect;
+use crate::WalletStandardDisconnect;
+
+/// The maximum duration of each wallet operation. Operations without a
+/// duration can wait forever.
+///
+/// Batch methods like `sign_messages` share the timeout of the single
+/// operation for the whole batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, TypedBuilder)]
+pub struct WalletTimeouts {
+	#[builder(default, setter(strip_option))]
+	pub connect: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub disconnect: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub sign_message: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub sign_transaction: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub sign_and_send_transaction: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub sign_in: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub encrypt: Option<Duration>,
+	#[builder(default, setter(strip_option))]
+	pub decrypt: Option<Duration>,
+}
+
+impl WalletTimeouts {
+	/// Use the same timeout for every operation.
+	pub const fn all(duration: Duration) -> Self {
+		Self {
+			connect: Some(duration),
+			disconnect: Some(duration),
+			sign_message: Some(duration),
+			sign_transaction: Some(duration),
+			sign_and_send_transaction: Some(duration),
+			sign_in: Some(duration),
+			encrypt: Some(duration),
+			decrypt: Some(duration),
+		}
+	}
+}
+
+#[derive(Debug, Default)]
+struct CancelHandleInner {
+	cancelled: bool,
+	senders: Vec<oneshot::Sender<()>>,
+}
+
+/// Cancels wallet operations, like an `AbortController`.
+///
+/// Once cancelled, the operations in progress and every later operation fail
+/// with [`WalletError::WalletCancelled`]. Replace the handle to run operations
+/// again. The handle can be cloned and shared between threads.
+#[derive(Clone, Debug, Default)]
+pub struct CancelHandle(Arc<Mutex<CancelHandleInner>>);
+
+impl CancelHandle {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Cancel the operations which use this handle.
+	pub fn cancel(&self) {
+		let senders = {
+			let mut inner = self.0.lock().unwrap();
+			inner.cancelled = true;
+			std::mem::take(&mut inner.senders)
+		};
+
+		for sender in senders {
+			sender.send(()).ok();
+		}
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.0.lock().unwrap().cancelled
+	}
+
+	/// Resolves once the handle has been cancelled.
+	pub fn cancelled(&self) -> impl Future<Output = ()> + Send + 'static {
+		let receiver = {
+			let mut inner = self.0.lock().unwrap();
+
+			if inner.cancelled {
+				None
+			} else {
+				let (sender, receiver) = oneshot::channel();
+				inner.senders.retain(|sender| !sender.is_canceled());
+				inner.senders.push(sender);
+				Some(receiver)
+			}
+		};
+
+		async move {
+			if let Some(receiver) = receiver {
+				// The sender is only dropped without sending when the handle is
+				// gone, in which case it can never be cancelled.
+				if receiver.await.is_err() {
+					future::pending::<()>().await;
+				}
+			}
+		}
+	}
+}
+
+/// Run the wallet operation until it completes, the `timeout` elapses or the
+/// `cancel` handle is cancelled.
+///
+/// The operation is dropped when it is interrupted, so none of the code after
+/// its pending `.await` runs. This works with any async runtime, including
+/// the browser.
+///
+/// # Errors
+///
+/// Returns [`WalletError::WalletTimeout`] when the timeout elapses and
+/// [`WalletError::WalletCancelled`] when the operation is cancelled.
+pub async fn with_timeout<T>(
+	operation: impl Future<Output = WalletResult<T>>,
+	timeout: Option<Duration>,
+	cancel: &CancelHandle,
+) -> WalletResult<T> {
+	let cancelled = cancel.cancelled();
+	let interrupted = async move {
+		let timer = async move {
+			match timeout {
+				Some(duration) => Delay::new(duration).await,
+				None => future::pending().await,
+			}
+		};
+
+		match future::select(pin!(timer), pin!(cancelled)).await {
+			Either::Left(_) => WalletError::WalletTimeout,
+			Either::Right(_) => WalletError::WalletCancelled,
+		}
+	};
+
+	// The interruption is polled first so that an operation never starts once
+	// the handle has been cancelled.
+	match future::select(pin!(interrupted), pin!(operation)).await {
+		Either::Left((error, _)) => Err(error),
+		Either::Right((result, _)) => result,
+	}
+}
+
+/// A wallet which applies [`WalletTimeouts`] and a [`CancelHandle`] to every
+/// operation.
+///
+/// An interrupted operation is dropped before it completes, so a `connect`
+/// which times out never sets the wallet account, even when the wallet
+/// approves the connection later. Wrap the wallet in a
+/// [`crate::StatefulWallet`] to move to [`crate::ConnectionState::Errored`]
+/// on timeouts.
+///
+/// ```rust
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use std::time::Duration;
+///
+/// use wallet_standard::TimeoutWallet;
+/// use wallet_standard::WalletTimeouts;
+/// use wallet_standard::prelude::*;
+/// use wallet_standard::testing::MockResponse;
+/// use wallet_standard::testing::MockWallet;
+///
+/// # futures::executor::block_on(async {
+/// let mock = MockWallet::default();
+/// mock.handle().push_connect(MockResponse::Pending);
+///
+/// let timeouts = WalletTimeouts::builder()
+/// 	.connect(Duration::from_millis(10))
+/// 	.build();
+/// let mut wallet = TimeoutWallet::new(mock, timeouts);
+///
+/// assert_eq!(wallet.connect().await, Err(WalletError::WalletTimeout));
+/// assert!(!wallet.connected());
+/// # })
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TimeoutWallet<W> {
+	wallet: W,
+	timeouts: WalletTimeouts,
+	cancel: CancelHandle,
+}
+
+impl<W> TimeoutWallet<W> {
+	pub fn new(wallet: W, timeouts: WalletTimeouts) -> Self {
+		Self {
+			wallet,
+			timeouts,
+			cancel: CancelHandle::new(),
+		}
+	}
+
+	pub fn timeouts(&self) -> WalletTimeouts {
+		self.timeouts
+	}
+
+	pub fn set_timeouts(&mut self, timeouts: WalletTimeouts) {
+		self.timeouts = timeouts;
+	}
+
+	/// The handle which cancels the operations of this wallet.
+	pub fn cancel_handle(&self) -> CancelHandle {
+		self.cancel.clone()
+	}
+
+	/// Use a new cancel handle, e.g. after the previous one has been
+	/// cancelled.
+	pub fn set_cancel_handle(&mut self, cancel: CancelHandle) {
+		self.cancel = cancel;
+	}
+
+	/// The wrapped wallet.
+	pub fn inner(&self) -> &W {
+		&self.wallet
+	}
+
+	pub fn into_inner(self) -> W {
+		self.wallet
+	}
+}
+
+impl<W: Wallet> Wallet for TimeoutWallet<W> {
+	type Account = W::Account;
+	type Wallet = W::Wallet;
+
+	fn wallet(&self) -> Self::Wallet {
+		self.wallet.wallet()
+	}
+
+	fn wallet_account(&self) -> Option<Self::Account> {
+		self.wallet.wallet_account()
+	}
+}
+
+impl<W: WalletAccounts> WalletAccounts for TimeoutWallet<W> {
+	fn wallet_accounts(&self) -> Vec<Self::Account> {
+		self.wallet.wallet_accounts()
+	}
+
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account> {
+		self.wallet.select_account(address)
+	}
+
+	fn sync_wallet_account(&mut self) -> Option<Self::Account> {
+		self.wallet.sync_wallet_account()
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletStandard> WalletStandardConnect for TimeoutWallet<W> {
+	async fn connect(&mut self) -> WalletResult<Vec<Self::Account>> {
+		self.connect_with_options(StandardConnectInput::default())
+			.await
+	}
+
+	async fn connect_with_options(
+		&mut self,
+		options: StandardConnectInput,
+	) -> WalletResult<Vec<Self::Account>> {
+		with_timeout(
+			self.wallet.connect_with_options(options),
+			self.timeouts.connect,
+			&self.cancel,
+		)
+		.await
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletStandard> WalletStandardDisconnect for TimeoutWallet<W> {
+	async fn disconnect(&mut self) -> WalletResult<()> {
+		with_timeout(
+			self.wallet.disconnect(),
+			self.timeouts.disconnect,
+			&self.cancel,
+		)
+		.await
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletExperimentalEncrypt> WalletExperimentalEncrypt for TimeoutWallet<W> {
+	type Output = W::Output;
+
+	async fn encrypt_many(
+		&self,
+		props: Vec<ExperimentalEncryptProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		with_timeout(
+			self.wallet.encrypt_many(props),
+			self.timeouts.encrypt,
+			&self.cancel,
+		)
+		.await
+	}
+
+	async fn encrypt(&self, props: ExperimentalEncryptProps) -> WalletResult<Self::Output> {
+		with_timeout(
+			self.wallet.encrypt(props),
+			self.timeouts.encrypt,
+			&self.cancel,
+		)
+		.await
+	}
+}
+
+#[async_trait(?Send)]
+impl<W: WalletExperimentalDecrypt> WalletExperimentalDecrypt for TimeoutWallet<W> {
+	type Output = W::Output;
+
+	async fn decrypt_many(
+		&self,
+		props: Vec<ExperimentalDecryptProps>,
+	) -> WalletResult<Vec<Self::Output>> {
+		with_timeout(
+			self.wallet.decrypt_many(props),
+			self.timeouts.decrypt,
+			&self.cancel,
+		)
+		.await
+	}
+
+	async fn decrypt(&self, props: ExperimentalDecryptProps) -> WalletResult<Self::Output> {
+		with_timeout(
+			self.wallet.decrypt(props),
+			self.timeouts.decrypt,
+			&self.cancel,
+		)
+		.await
+	}
+}
+
+#[cfg(feature = "solana")]
+mod solana {
+	use async_trait::async_trait;
+
+	use super::TimeoutWallet;
+	use super::with_timeout;
+	use crate::SolanaSignAndSendTransactionProps;
+	use crate::SolanaSignInInput;
+	use crate::SolanaSignTransactionProps;
+	use crate::WalletResult;
+	use crate::WalletSolanaSignAndSendTransaction;
+	use crate::WalletSolanaSignIn;
+	use crate::WalletSolanaSignMessage;
+	use crate::WalletSolanaSignTransaction;
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignMessage> WalletSolanaSignMessage for TimeoutWallet<W> {
+		type Output = W::Output;
+
+		async fn sign_message_async(
+			&self,
+			message: impl Into<Vec<u8>>,
+		) -> WalletResult<Self::Output> {
+			with_timeout(
+				self.wallet.sign_message_async(message),
+				self.timeouts.sign_message,
+				&self.cancel,
+			)
+			.await
+		}
+
+		async fn sign_messages<M: Into<Vec<u8>>>(
+			&self,
+			messages: Vec<M>,
+		) -> WalletResult<Vec<Self::Output>> {
+			with_timeout(
+				self.wallet.sign_messages(messages),
+				self.timeouts.sign_message,
+				&self.cancel,
+			)
+			.await
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignTransaction> WalletSolanaSignTransaction for TimeoutWallet<W> {
+		type Output = W::Output;
+
+		async fn sign_transaction(
+			&self,
+			props: SolanaSignTransactionProps,
+		) -> WalletResult<Self::Output> {
+			with_timeout(
+				self.wallet.sign_transaction(props),
+				self.timeouts.sign_transaction,
+				&self.cancel,
+			)
+			.await
+		}
+
+		async fn sign_transactions(
+			&self,
+			inputs: Vec<SolanaSignTransactionProps>,
+		) -> WalletResult<Vec<Self::Output>> {
+			with_timeout(
+				self.wallet.sign_transactions(inputs),
+				self.timeouts.sign_transaction,
+				&self.cancel,
+			)
+			.await
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignAndSendTransaction> WalletSolanaSignAndSendTransaction
+		for TimeoutWallet<W>
+	{
+		type Output = W::Output;
+
+		async fn sign_and_send_transaction(
+			&self,
+			props: SolanaSignAndSendTransactionProps,
+		) -> WalletResult<Self::Output> {
+			with_timeout(
+				self.wallet.sign_and_send_transaction(props),
+				self.timeouts.sign_and_send_transaction,
+				&self.cancel,
+			)
+			.await
+		}
+
+		async fn sign_and_send_transactions(
+			&self,
+			inputs: Vec<SolanaSignAndSendTransactionProps>,
+		) -> WalletResult<Vec<Self::Output>> {
+			with_timeout(
+				self.wallet.sign_and_send_transactions(inputs),
+				self.timeouts.sign_and_send_transaction,
+				&self.cancel,
+			)
+			.await
+		}
+	}
+
+	#[async_trait(?Send)]
+	impl<W: WalletSolanaSignIn> WalletSolanaSignIn for TimeoutWallet<W> {
+		type Output = W::Output;
+
+		async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
+			with_timeout(
+				self.wallet.sign_in(input),
+				self.timeouts.sign_in,
+				&self.cancel,
+			)
+			.await
+		}
+
+		async fn sign_in_many(
+			&self,
+			inputs: Vec<SolanaSignInInput>,
+		) -> WalletResult<Vec<Self::Output>> {
+			with_timeout(
+				self.wallet.sign_in_many(inputs),
+				self.timeouts.sign_in,
+				&self.cancel,
+			)
+			.await
+		}
+	}
+}
+
+#[cfg(feature = "testing")]
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+	use futures::poll;
+
+	use super::*;
+	use crate::ConnectionState;
+	use crate::StatefulWallet;
+	use crate::WalletSolanaSignMessage;
+	use crate::testing::MockResponse;
+	use crate::testing::MockWallet;
+
+	#[test]
+	fn timed_out_connect_never_sets_the_account() {
+		let mock = MockWallet::default();
+		mock.handle().push_connect(MockResponse::Pending);
+		let timeouts = WalletTimeouts::builder().connect(Duration::ZERO).build();
+		let mut wallet = StatefulWallet::new(TimeoutWallet::new(mock, timeouts));
+
+		assert_eq!(block_on(wallet.connect()), Err(WalletError::WalletTimeout));
+		assert_eq!(
+			wallet.state(),
+			ConnectionState::Errored(WalletError::WalletTimeout)
+		);
+		assert!(!wallet.connected());
+		assert!(!wallet.inner().inner().connected());
+	}
+
+	#[test]
+	fn cancel_interrupts_pending_and_later_operations() -> WalletResult<()> {
+		let mock = MockWallet::default();
+		mock.handle().push_sign_message(MockResponse::Pending);
+		let mut wallet = TimeoutWallet::new(mock, WalletTimeouts::default());
+		block_on(wallet.connect())?;
+
+		let cancel = wallet.cancel_handle();
+
+		block_on(async {
+			let mut operation = pin!(wallet.sign_message_async("hello"));
+			assert!(poll!(operation.as_mut()).is_pending());
+
+			cancel.cancel();
+			assert_eq!(operation.await, Err(WalletError::WalletCancelled));
+		});
+		assert_eq!(
+			block_on(wallet.sign_message_async("hello")),
+			Err(WalletError::WalletCancelled)
+		);
+
+		wallet.set_cancel_handle(CancelHandle::new());
+		assert!(block_on(wallet.sign_message_async("hello")).is_ok());
+
+		Ok(())
+	}
+}
@@ -1,5 +1,10 @@
+use crate::ChainId;
+use crate::FeatureName;
+use crate::WalletIcon;
+use crate::WalletResult;
 use crate::WalletStandardConnect;
 use crate::WalletStandardDisconnect;
+use crate::identifier::parse_all;
 
 /// Provides information about a wallet implementation.
 ///
@@ -68,7 +73,7 @@ pub trait WalletInfo {
 	/// # Example
 	///
 	/// ```
-	/// vec!["solana:mainnet".to_string(), "solana:devnet".to_string()]
+	/// let chains = vec!["solana:mainnet".to_string(), "solana:devnet".to_string()];
 	/// ```
 	fn chains(&self) -> Vec<String>;
 	/// Features supported by the Wallet.
@@ -107,12 +112,12 @@ pub trait WalletInfo {
 	/// # Example
 	///
 	/// ```
-	/// vec![
+	/// let features = vec![
 	/// 	"standard:connect".to_string(),
 	/// 	"standard:disconnect".to_string(),
 	/// 	"solana:signMessage".to_string(),
 	/// 	"solana:signTransaction".to_string(),
-	/// ]
+	/// ];
 	/// ```
 	fn features(&self) -> Vec<String>;
 	/// {@link `WalletAccount` | Accounts} that the app is authorized to use.
@@ -127,6 +132,31 @@ pub trait WalletInfo {
 	/// The {@link "@wallet-standard/features".EventsFeature | `standard:events`
 	/// feature} should be used to notify the app if the value changes.
 	fn accounts(&self) -> Vec<Self::Account>;
+	/// The [`chains`](WalletInfo::chains) of the wallet parsed as [`ChainId`]s.
+	///
+	/// Fails with
+	/// [`WalletError::InvalidIdentifier`](crate::WalletError::InvalidIdentifier)
+	/// when any chain is not a valid identifier or uses a reserved namespace.
+	fn try_chains(&self) -> WalletResult<Vec<ChainId>> {
+		parse_all(&self.chains())
+	}
+	/// The [`features`](WalletInfo::features) of the wallet parsed as
+	/// [`FeatureName`]s.
+	///
+	/// Fails with
+	/// [`WalletError::InvalidIdentifier`](crate::WalletError::InvalidIdentifier)
+	/// when any feature name is not a valid identifier.
+	fn try_features(&self) -> WalletResult<Vec<FeatureName>> {
+		parse_all(&self.features())
+	}
+	/// The [`icon`](WalletInfo::icon) of the wallet parsed as a
+	/// [`WalletIcon`].
+	///
+	/// Fails when the icon is not a base64 encoded data URL of a supported
+	/// image type.
+	fn try_icon(&self) -> WalletResult<WalletIcon> {
+		WalletIcon::parse(&self.icon())
+	}
 }
 
 /// Interface of a **`WalletAccount`**, also referred to as an **Account**.
@@ -181,7 +211,7 @@ pub trait WalletAccountInfo {
 	///
 	/// ```
 	/// // A 32-byte Ed25519 public key
-	/// vec![0, 1, 2, 3 /* ... */]
+	/// let public_key = vec![0, 1, 2, 3 /* ... */];
 	/// ```
 	fn public_key(&self) -> Vec<u8>;
 	/// Chains supported by the account.
@@ -192,7 +222,7 @@ pub trait WalletAccountInfo {
 	/// # Example
 	///
 	/// ```
-	/// vec!["solana:mainnet".to_string()]
+	/// let chains = vec!["solana:mainnet".to_string()];
 	/// ```
 	fn chains(&self) -> Vec<String>;
 	/// Feature names supported by the account.
@@ -203,10 +233,10 @@ pub trait WalletAccountInfo {
 	/// # Example
 	///
 	/// ```
-	/// vec![
+	/// let features = vec![
 	/// 	"solana:signMessage".to_string(),
 	/// 	"solana:signTransaction".to_string(),
-	/// ]
+	/// ];
 	/// ```
 	fn features(&self) -> Vec<String>;
 	/// Optional user-friendly descriptive label or name for the account. This
@@ -215,7 +245,7 @@ pub trait WalletAccountInfo {
 	/// # Example
 	///
 	/// ```
-	/// Some("Main Account".to_string())
+	/// let label = Some("Main Account".to_string());
 	/// ```
 	fn label(&self) -> Option<String>;
 	/// Optional user-friendly icon for the account. This may be displayed by
@@ -226,9 +256,32 @@ pub trait WalletAccountInfo {
 	/// # Example
 	///
 	/// ```
-	/// Some("data:image/svg+xml;base64,...".to_string())
+	/// let icon = Some("data:image/svg+xml;base64,...".to_string());
 	/// ```
 	fn icon(&self) -> Option<String>;
+	/// The [`chains`](WalletAccountInfo::chains) of the account parsed as
+	/// [`ChainId`]s.
+	///
+	/// Fails with
+	/// [`WalletError::InvalidIdentifier`](crate::WalletError::InvalidIdentifier)
+	/// when any chain is not a valid identifier or uses a reserved namespace.
+	fn try_chains(&self) -> WalletResult<Vec<ChainId>> {
+		parse_all(&self.chains())
+	}
+	/// The [`features`](WalletAccountInfo::features) of the account parsed as
+	/// [`FeatureName`]s.
+	///
+	/// Fails with
+	/// [`WalletError::InvalidIdentifier`](crate::WalletError::InvalidIdentifier)
+	/// when any feature name is not a valid identifier.
+	fn try_features(&self) -> WalletResult<Vec<FeatureName>> {
+		parse_all(&self.features())
+	}
+	/// The [`icon`](WalletAccountInfo::icon) of the account parsed as a
+	/// [`WalletIcon`], if it has one.
+	fn try_icon(&self) -> WalletResult<Option<WalletIcon>> {
+		self.icon().as_deref().map(WalletIcon::parse).transpose()
+	}
 }
 
 /// The core trait for wallet implementations.
@@ -0,0 +1,232 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::Stream;
+use futures::StreamExt;
+use futures::channel::mpsc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::StandardEventsChange;
+use crate::WalletAccountInfo;
+use crate::WalletEventEmitter;
+use crate::WalletEventSubscription;
+
+/// A typed change to the properties of a wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WalletEvent<A> {
+	/// Accounts were authorized or revoked. Accounts are compared by address.
+	AccountsChanged { added: Vec<A>, removed: Vec<A> },
+	/// The new chains of the wallet.
+	ChainsChanged { chains: Vec<String> },
+	/// The new features of the wallet.
+	FeaturesChanged { features: Vec<String> },
+}
+
+/// Converts `change` events into [`WalletEvent`]s by diffing the accounts of
+/// each change against the previous snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalletEventTracker<A> {
+	accounts: Vec<A>,
+}
+
+impl<A: WalletAccountInfo + Clone> WalletEventTracker<A> {
+	/// Start tracking from the currently authorized accounts.
+	pub fn new(accounts: Vec<A>) -> Self {
+		Self { accounts }
+	}
+
+	/// The accounts of the latest snapshot.
+	pub fn accounts(&self) -> &[A] {
+		&self.accounts
+	}
+
+	/// The events described by the `change`, in the order accounts, chains
+	/// and features. Accounts which only changed their properties don't
+	/// produce an event.
+	pub fn track(&mut self, change: StandardEventsChange<A>) -> Vec<WalletEvent<A>> {
+		let mut events = Vec::new();
+
+		if let Some(accounts) = change.accounts {
+			let added = difference(&accounts, &self.accounts);
+			let removed = difference(&self.accounts, &accounts);
+			self.accounts = accounts;
+
+			if !added.is_empty() || !removed.is_empty() {
+				events.push(WalletEvent::AccountsChanged { added, removed });
+			}
+		}
+
+		if let Some(chains) = change.chains {
+			events.push(WalletEvent::ChainsChanged { chains });
+		}
+
+		if let Some(features) = change.features {
+			events.push(WalletEvent::FeaturesChanged { features });
+		}
+
+		events
+	}
+}
+
+/// The accounts of `left` with an address which isn't in `right`.
+fn difference<A: WalletAccountInfo + Clone>(left: &[A], right: &[A]) -> Vec<A> {
+	let addresses = right.iter().map(A::address).collect::<Vec<_>>();
+
+	left.iter()
+		.filter(|account| !addresses.contains(&account.address()))
+		.cloned()
+		.collect()
+}
+
+/// A [`Stream`] of [`WalletEvent`]s.
+///
+/// The `guard` keeps the underlying listener registered and removes it when
+/// the stream is dropped.
+#[must_use = "streams do nothing unless polled"]
+pub struct WalletEventStream<A, G = WalletEventSubscription> {
+	receiver: mpsc::UnboundedReceiver<StandardEventsChange<A>>,
+	tracker: WalletEventTracker<A>,
+	pending: VecDeque<WalletEvent<A>>,
+	_guard: G,
+}
+
+impl<A: WalletAccountInfo + Clone, G> WalletEventStream<A, G> {
+	/// Create a stream from a channel of changes, starting from the currently
+	/// authorized `accounts`.
+	pub fn new(
+		receiver: mpsc::UnboundedReceiver<StandardEventsChange<A>>,
+		accounts: Vec<A>,
+		guard: G,
+	) -> Self {
+		Self {
+			receiver,
+			tracker: WalletEventTracker::new(accounts),
+			pending: VecDeque::new(),
+			_guard: guard,
+		}
+	}
+}
+
+impl<A, G> Unpin for WalletEventStream<A, G> {}
+
+impl<A: WalletAccountInfo + Clone, G> Stream for WalletEventStream<A, G> {
+	type Item = WalletEvent<A>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		loop {
+			if let Some(event) = this.pending.pop_front() {
+				return Poll::Ready(Some(event));
+			}
+
+			match this.receiver.poll_next_unpin(cx) {
+				Poll::Ready(Some(change)) => this.pending.extend(this.tracker.track(change)),
+				Poll::Ready(None) => return Poll::Ready(None),
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+}
+
+impl<A, G> std::fmt::Debug for WalletEventStream<A, G> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("WalletEventStream")
+			.field("pending", &self.pending.len())
+			.finish_non_exhaustive()
+	}
+}
+
+impl<A: WalletAccountInfo + Clone + Send + 'static> WalletEventEmitter<A> {
+	/// A stream of every change emitted after this call, starting from the
+	/// currently authorized `accounts`.
+	///
+	/// The stream ends once every clone of the emitter has been dropped.
+	pub fn stream(&self, accounts: Vec<A>) -> WalletEventStream<A> {
+		let (sender, receiver) = mpsc::unbounded();
+		let subscription = self.subscribe(move |change| {
+			sender.unbounded_send(change.clone()).ok();
+		});
+
+		WalletEventStream::new(receiver, accounts, subscription)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::executor::block_on;
+
+	use super::*;
+	use crate::WalletAccountSnapshot;
+
+	fn account(address: &str) -> WalletAccountSnapshot {
+		WalletAccountSnapshot::builder()
+			.address(address)
+			.public_key(vec![])
+			.build()
+	}
+
+	#[test]
+	fn stream_diffs_accounts_against_the_previous_snapshot() {
+		let emitter = WalletEventEmitter::new();
+		let mut events = emitter.stream(vec![account("a")]);
+
+		emitter.emit(
+			&StandardEventsChange::builder()
+				.accounts(vec![account("a"), account("b")])
+				.chains(vec!["solana:devnet".to_string()])
+				.build(),
+		);
+		emitter.emit(
+			&StandardEventsChange::builder()
+				.accounts(vec![account("c")])
+				.features(vec!["standard:connect".to_string()])
+				.build(),
+		);
+		drop(emitter);
+
+		let events = block_on(async {
+			let mut collected = vec![];
+
+			while let Some(event) = events.next().await {
+				collected.push(event);
+			}
+
+			collected
+		});
+
+		assert_eq!(
+			events,
+			[
+				WalletEvent::AccountsChanged {
+					added: vec![account("b")],
+					removed: vec![],
+				},
+				WalletEvent::ChainsChanged {
+					chains: vec!["solana:devnet".to_string()],
+				},
+				WalletEvent::AccountsChanged {
+					added: vec![account("c")],
+					removed: vec![account("a"), account("b")],
+				},
+				WalletEvent::FeaturesChanged {
+					features: vec!["standard:connect".to_string()],
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn dropping_the_stream_unsubscribes() {
+		let emitter = WalletEventEmitter::<WalletAccountSnapshot>::new();
+		let events = emitter.stream(vec![]);
+
+		assert_eq!(emitter.listener_count(), 1);
+		drop(events);
+		assert_eq!(emitter.listener_count(), 0);
+	}
+}
@@ -0,0 +1,46 @@
+use wallet_standard::CancelHandle;
+use wallet_standard::WalletResult;
+use wasm_bindgen::prelude::*;
+use web_sys::AbortSignal;
+
+/// Cancels a [`CancelHandle`] when an `AbortSignal` is aborted, e.g. to
+/// cancel a [`wallet_standard::TimeoutWallet`] from JavaScript.
+///
+/// The listener is removed when this is dropped.
+///
+/// ```rust,ignore
+/// let controller = web_sys::AbortController::new()?;
+/// let _listener = AbortSignalListener::new(&controller.signal(), wallet.cancel_handle())?;
+///
+/// controller.abort();
+/// ```
+pub struct AbortSignalListener {
+	signal: AbortSignal,
+	callback: Closure<dyn Fn()>,
+}
+
+impl AbortSignalListener {
+	/// Listen to the `signal`. The `handle` is cancelled immediately when the
+	/// signal has already been aborted.
+	pub fn new(signal: &AbortSignal, handle: CancelHandle) -> WalletResult<Self> {
+		if signal.aborted() {
+			handle.cancel();
+		}
+
+		let callback = Closure::<dyn Fn()>::new(move || handle.cancel());
+		signal.add_event_listener_with_callback("abort", callback.as_ref().unchecked_ref())?;
+
+		Ok(Self {
+			signal: signal.clone(),
+			callback,
+		})
+	}
+}
+
+impl Drop for AbortSignalListener {
+	fn drop(&mut self) {
+		let _ = self
+			.signal
+			.remove_event_listener_with_callback("abort", self.callback.as_ref().unchecked_ref());
+	}
+}
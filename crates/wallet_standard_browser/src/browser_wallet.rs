@@ -1,5 +1,9 @@
 use typed_builder::TypedBuilder;
 use wallet_standard::Wallet;
+use wallet_standard::WalletAccounts;
+use wallet_standard::WalletInfo;
+use wallet_standard::WalletResult;
+use wallet_standard::reconcile_wallet_account;
 
 use crate::BrowserWalletAccountInfo;
 use crate::BrowserWalletInfo;
@@ -9,6 +13,9 @@ pub struct BrowserWallet {
 	/// The currently selected wallet account.
 	#[builder(default, setter(strip_option))]
 	pub wallet_account: Option<BrowserWalletAccountInfo>,
+	/// Every account which the app is authorized to use.
+	#[builder(default)]
+	pub accounts: Vec<BrowserWalletAccountInfo>,
 	/// The currently selected wallet.
 	pub wallet: BrowserWalletInfo,
 }
@@ -26,6 +33,36 @@ impl Wallet for BrowserWallet {
 	}
 }
 
+impl WalletAccounts for BrowserWallet {
+	fn wallet_accounts(&self) -> Vec<Self::Account> {
+		self.accounts.clone()
+	}
+
+	fn select_account(&mut self, address: &str) -> WalletResult<Self::Account> {
+		let account = self.find_account(address)?;
+		self.wallet_account = Some(account.clone());
+
+		Ok(account)
+	}
+
+	/// Reload the authorized accounts from the wallet before reconciling the
+	/// active account. Call this from a `change` event listener.
+	fn sync_wallet_account(&mut self) -> Option<Self::Account> {
+		if self.wallet_account.is_some() {
+			self.accounts = self.wallet.accounts();
+		}
+
+		self.wallet_account =
+			reconcile_wallet_account(self.wallet_account.as_ref(), &self.accounts);
+
+		if self.wallet_account.is_none() {
+			self.accounts.clear();
+		}
+
+		self.wallet_account.clone()
+	}
+}
+
 impl From<BrowserWalletInfo> for BrowserWallet {
 	fn from(value: BrowserWalletInfo) -> Self {
 		BrowserWallet::builder().wallet(value).build()
@@ -8,11 +8,16 @@ use js_sys::Array;
 use js_sys::Function;
 use js_sys::Object;
 use js_sys::Reflect;
+use semver::Version;
+use semver::VersionReq;
 use serde::Deserialize;
 use serde::Serialize;
 use typed_builder::TypedBuilder;
 use wallet_standard::WalletAccountInfo;
+use wallet_standard::WalletAccountSnapshot;
 use wallet_standard::WalletError;
+#[cfg(feature = "svg")]
+use wallet_standard::WalletIcon;
 use wallet_standard::WalletInfo;
 use wallet_standard::WalletResult;
 use wasm_bindgen::JsCast;
@@ -23,6 +28,7 @@ use crate::FeatureFromJs;
 use crate::StandardConnectFeature;
 use crate::StandardDisconnectFeature;
 use crate::StandardEventsFeature;
+use crate::types::feature_version_string;
 
 #[wasm_bindgen(module = "/js/wallet.js")]
 extern "C" {
@@ -257,18 +263,63 @@ impl BrowserWalletInfo {
 		T::feature_from_js_object(&self.features_object())
 	}
 
-	/// Get the required feature and throw an error if it isn't supported.
+	/// Get the required feature and throw an error if it isn't supported or
+	/// its version is older than `T::MIN_VERSION`. Features with a missing,
+	/// non-semver or prerelease version are returned.
 	pub fn get_feature<T: FeatureFromJs>(&self) -> WalletResult<T> {
+		let feature = self.get_provided_feature::<T>()?;
+
+		if feature.is_older_than_min_version() {
+			return Err(WalletError::UnsupportedFeatureVersion {
+				feature: T::NAME.to_string(),
+				wallet: self.name(),
+				version: feature.feature_version_string().unwrap_or_default(),
+				required: T::version_requirement().to_string(),
+			});
+		}
+
+		Ok(feature)
+	}
+
+	/// Get the required feature and throw an error if it isn't supported or
+	/// its version doesn't match the provided requirement. Unlike
+	/// `get_feature`, a missing or non-semver version never matches.
+	pub fn get_feature_with_version<T: FeatureFromJs>(
+		&self,
+		requirement: &VersionReq,
+	) -> WalletResult<T> {
+		let feature = self.get_provided_feature::<T>()?;
+
+		match feature.feature_version() {
+			Some(version) if requirement.matches(&version) => Ok(feature),
+			version => {
+				Err(WalletError::UnsupportedFeatureVersion {
+					feature: T::NAME.to_string(),
+					wallet: self.name(),
+					version: version
+						.map(|version| version.to_string())
+						.or_else(|| feature.feature_version_string())
+						.unwrap_or_default(),
+					required: requirement.to_string(),
+				})
+			}
+		}
+	}
+
+	/// The feature when the wallet provides it, regardless of its version.
+	fn get_provided_feature<T: FeatureFromJs>(&self) -> WalletResult<T> {
 		self.get_feature_option::<T>()
+			.filter(|feature| !feature.as_ref().is_undefined() && !feature.as_ref().is_null())
 			.ok_or(WalletError::UnsupportedFeature {
 				feature: T::NAME.to_string(),
 				wallet: self.name(),
 			})
 	}
 
-	/// Check whether a feature is supported by the given wallet.
+	/// Check whether a feature is supported by the given wallet. This agrees
+	/// with `get_feature`.
 	pub fn is_feature_supported<T: FeatureFromJs>(&self) -> bool {
-		self.get_feature_option::<T>().is_some()
+		self.get_feature::<T>().is_ok()
 	}
 
 	pub fn is_standard_compatible(&self) -> bool {
@@ -276,6 +327,68 @@ impl BrowserWalletInfo {
 			&& self.is_feature_supported::<StandardEventsFeature>()
 			&& self.is_feature_supported::<StandardDisconnectFeature>()
 	}
+
+	/// List every feature supported by the wallet with its version.
+	pub fn capabilities(&self) -> Vec<FeatureCapability> {
+		let features = self.features_object();
+
+		self.features()
+			.into_iter()
+			.map(|name| {
+				let raw_version = Reflect::get(&features, &JsValue::from_str(&name))
+					.ok()
+					.and_then(|feature| feature_version_string(&feature));
+				let version = raw_version
+					.as_deref()
+					.and_then(|version| Version::parse(version).ok());
+
+				FeatureCapability {
+					name,
+					version,
+					raw_version,
+				}
+			})
+			.collect()
+	}
+
+	/// Check whether the icon is a valid data URL of a supported image type.
+	/// Use `try_icon()` for the reason it is invalid.
+	pub fn has_valid_icon(&self) -> bool {
+		self.try_icon().is_ok()
+	}
+
+	/// The icon with scripts, event handlers, `foreignObject` elements and
+	/// external references removed. This is safe to render inline.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the icon is invalid or can't be made safe.
+	#[cfg(feature = "svg")]
+	pub fn sanitized_icon(&self) -> WalletResult<WalletIcon> {
+		self.try_icon()?.sanitize()
+	}
+}
+
+/// A feature supported by a wallet and its version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureCapability {
+	/// The name of the feature, e.g. `standard:connect`.
+	pub name: String,
+	/// The parsed version of the feature. This is `None` when the wallet
+	/// didn't provide a valid semver version.
+	pub version: Option<Version>,
+	/// The version exactly as it was provided by the wallet.
+	pub raw_version: Option<String>,
+}
+
+impl FeatureCapability {
+	/// Whether the version of the feature matches the requirement.
+	pub fn matches(&self, requirement: &VersionReq) -> bool {
+		self.version
+			.as_ref()
+			.is_some_and(|version| requirement.matches(version))
+	}
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -374,6 +487,25 @@ pub struct BrowserWalletAccountInfoProps {
 	pub icon: Option<String>,
 }
 
+impl From<WalletAccountSnapshot> for BrowserWalletAccountInfoProps {
+	fn from(snapshot: WalletAccountSnapshot) -> Self {
+		Self {
+			address: snapshot.address,
+			public_key: snapshot.public_key,
+			chains: snapshot.chains,
+			features: snapshot.features,
+			label: snapshot.label,
+			icon: snapshot.icon,
+		}
+	}
+}
+
+impl From<&BrowserWalletAccountInfo> for BrowserWalletAccountInfoProps {
+	fn from(account: &BrowserWalletAccountInfo) -> Self {
+		WalletAccountSnapshot::from_account(account).into()
+	}
+}
+
 impl BrowserWalletAccountInfo {
 	/// Create a new `BrowserWalletAccountInfo` from a
 	/// `BrowserWalletAccountInfoProps`.
@@ -386,6 +518,17 @@ impl BrowserWalletAccountInfo {
 
 		Ok(result)
 	}
+
+	/// The icon of the account with scripts, event handlers, `foreignObject`
+	/// elements and external references removed, if it has one.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the icon is invalid or can't be made safe.
+	#[cfg(feature = "svg")]
+	pub fn sanitized_icon(&self) -> WalletResult<Option<WalletIcon>> {
+		self.try_icon()?.map(|icon| icon.sanitize()).transpose()
+	}
 }
 
 impl WalletAccountInfo for BrowserWalletAccountInfo {
@@ -11,6 +11,7 @@ use wallet_standard::ExperimentalDecryptProps;
 use wallet_standard::WalletError;
 use wallet_standard::WalletExperimentalDecrypt;
 use wallet_standard::WalletResult;
+use wallet_standard::expect_outputs;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
@@ -91,7 +92,10 @@ impl WalletExperimentalDecrypt for BrowserWallet {
 		let inputs: Array = serde_wasm_bindgen::to_value(&input)?.unchecked_into();
 		let result: Array = feature.decrypt(inputs).await?.unchecked_into();
 
-		Ok(result.into_iter().map(JsCast::unchecked_into).collect())
+		expect_outputs(
+			input.len(),
+			result.into_iter().map(JsCast::unchecked_into).collect(),
+		)
 	}
 
 	async fn decrypt(&self, props: ExperimentalDecryptProps) -> WalletResult<Self::Output> {
@@ -11,6 +11,7 @@ use wallet_standard::ExperimentalEncryptProps;
 use wallet_standard::WalletError;
 use wallet_standard::WalletExperimentalEncrypt;
 use wallet_standard::WalletResult;
+use wallet_standard::expect_outputs;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
@@ -98,10 +99,13 @@ impl WalletExperimentalEncrypt for BrowserWallet {
 		let inputs: Array = serde_wasm_bindgen::to_value(&input)?.unchecked_into();
 		let result: Array = feature.encrypt(inputs).await?.unchecked_into();
 
-		Ok(result
-			.into_iter()
-			.map(wasm_bindgen::JsCast::unchecked_into)
-			.collect())
+		expect_outputs(
+			input.len(),
+			result
+				.into_iter()
+				.map(wasm_bindgen::JsCast::unchecked_into)
+				.collect(),
+		)
 	}
 
 	async fn encrypt(&self, props: ExperimentalEncryptProps) -> WalletResult<Self::Output> {
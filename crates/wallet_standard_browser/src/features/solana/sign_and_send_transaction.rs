@@ -13,6 +13,7 @@ use wallet_standard::SolanaSignatureOutput;
 use wallet_standard::WalletError;
 use wallet_standard::WalletResult;
 use wallet_standard::WalletSolanaSignAndSendTransaction;
+use wallet_standard::expect_outputs;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
@@ -125,10 +126,13 @@ impl SolanaSignAndSendTransactionFeature {
 			.await?
 			.dyn_into()?;
 
-		Ok(js_results
-			.into_iter()
-			.map(wasm_bindgen::JsCast::unchecked_into)
-			.collect())
+		expect_outputs(
+			inputs.len(),
+			js_results
+				.into_iter()
+				.map(wasm_bindgen::JsCast::unchecked_into)
+				.collect(),
+		)
 	}
 }
 
@@ -4,18 +4,26 @@ use async_trait::async_trait;
 use js_sys::Array;
 use solana_signature::Signature;
 use wallet_standard::SOLANA_SIGN_IN;
+use wallet_standard::SignInDomain;
+use wallet_standard::SignMessageSignInOutput;
 use wallet_standard::SolanaSignInInput;
 use wallet_standard::SolanaSignInOutput;
 use wallet_standard::SolanaSignMessageOutput;
 use wallet_standard::SolanaSignatureOutput;
+use wallet_standard::Wallet;
 use wallet_standard::WalletError;
 use wallet_standard::WalletResult;
 use wallet_standard::WalletSolanaPubkey;
 use wallet_standard::WalletSolanaSignIn;
+use wallet_standard::WalletStandardConnect;
+use wallet_standard::connect_and_sign_in_with_sign_message;
+use wallet_standard::expect_outputs;
+use wallet_standard::sign_in_with_sign_message;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
 
+use crate::BrowserSolanaSignMessageOutput;
 use crate::BrowserWallet;
 use crate::BrowserWalletAccountInfo;
 use crate::impl_feature_from_js;
@@ -96,16 +104,135 @@ impl SolanaSignInFeature {
 		let args: Array = serde_wasm_bindgen::to_value(&inputs)?.dyn_into()?;
 		let results: Array = self._sign_in(args).await?.dyn_into()?;
 
-		Ok(results
-			.into_iter()
-			.map(wasm_bindgen::JsCast::unchecked_into)
-			.collect())
+		expect_outputs(
+			inputs.len(),
+			results
+				.into_iter()
+				.map(wasm_bindgen::JsCast::unchecked_into)
+				.collect(),
+		)
+	}
+}
+
+/// The output of signing in with a [`BrowserWallet`].
+#[derive(Clone, Debug)]
+pub enum BrowserSignInOutput {
+	/// The wallet signed in with `solana:signIn`.
+	SignIn(BrowserSolanaSignInOutput),
+	/// The wallet doesn't support `solana:signIn`, so the sign in message was
+	/// signed with `solana:signMessage`.
+	SignMessage(SignMessageSignInOutput<BrowserWalletAccountInfo, BrowserSolanaSignMessageOutput>),
+}
+
+impl SolanaSignatureOutput for BrowserSignInOutput {
+	fn try_signature(&self) -> WalletResult<Signature> {
+		match self {
+			Self::SignIn(output) => output.try_signature(),
+			Self::SignMessage(output) => output.try_signature(),
+		}
+	}
+
+	fn signature(&self) -> Signature {
+		self.try_signature().unwrap_throw()
+	}
+}
+
+impl SolanaSignMessageOutput for BrowserSignInOutput {
+	fn signed_message(&self) -> Vec<u8> {
+		match self {
+			Self::SignIn(output) => output.signed_message(),
+			Self::SignMessage(output) => output.signed_message(),
+		}
+	}
+
+	fn signature_type(&self) -> Option<String> {
+		match self {
+			Self::SignIn(output) => output.signature_type(),
+			Self::SignMessage(output) => output.signature_type(),
+		}
 	}
 }
 
+impl SolanaSignInOutput for BrowserSignInOutput {
+	type Account = BrowserWalletAccountInfo;
+
+	fn account(&self) -> Self::Account {
+		match self {
+			Self::SignIn(output) => output.account(),
+			Self::SignMessage(output) => output.account(),
+		}
+	}
+}
+
+/// The host of the current page, which wallets use as the domain when the
+/// input doesn't provide one.
+fn location_domain() -> Option<String> {
+	let host = web_sys::window()?.location().host().ok()?;
+
+	SignInDomain::new(host).ok().map(String::from)
+}
+
+impl BrowserWallet {
+	/// Connect when the wallet has no account and then sign in. Wallets which
+	/// don't support `solana:signIn` sign in through
+	/// [`connect_and_sign_in_with_sign_message`], with a missing domain filled
+	/// in with the host of the page.
+	pub async fn connect_and_sign_in(
+		&mut self,
+		input: SolanaSignInInput,
+	) -> WalletResult<BrowserSignInOutput> {
+		match self.wallet.get_feature::<SolanaSignInFeature>() {
+			Ok(_) => {
+				if self.wallet_account().is_none() {
+					WalletStandardConnect::connect(self).await?;
+				}
+
+				self.sign_in(input).await
+			}
+			Err(WalletError::UnsupportedFeature { .. }) => {
+				let input = SolanaSignInInput {
+					domain: input.domain.or_else(location_domain),
+					..input
+				};
+				let output = connect_and_sign_in_with_sign_message(self, input).await?;
+
+				Ok(BrowserSignInOutput::SignMessage(output))
+			}
+			Err(error) => Err(error),
+		}
+	}
+
+	/// Sign in by signing the sign in message text with `solana:signMessage`,
+	/// for wallets which don't support `solana:signIn`.
+	async fn sign_in_with_sign_message(
+		&self,
+		inputs: Vec<SolanaSignInInput>,
+	) -> WalletResult<Vec<BrowserSignInOutput>> {
+		if inputs.is_empty() {
+			return Err(WalletError::InvalidArguments);
+		}
+
+		let mut outputs = Vec::with_capacity(inputs.len());
+
+		for input in inputs {
+			let input = SolanaSignInInput {
+				domain: input.domain.or_else(location_domain),
+				..input
+			};
+			let output = sign_in_with_sign_message(self, input).await?;
+			outputs.push(BrowserSignInOutput::SignMessage(output));
+		}
+
+		Ok(outputs)
+	}
+}
+
+/// Signing in requires a connected account and fails with
+/// [`WalletError::WalletAccount`] otherwise. Use
+/// [`BrowserWallet::connect_and_sign_in`] to connect first when needed.
 #[async_trait(?Send)]
 impl WalletSolanaSignIn for BrowserWallet {
-	type Output = BrowserSolanaSignInOutput;
+	type Output = BrowserSignInOutput;
 
 	async fn sign_in(&self, input: SolanaSignInInput) -> WalletResult<Self::Output> {
 		self.sign_in_many(vec![input])
@@ -115,6 +242,8 @@ impl WalletSolanaSignIn for BrowserWallet {
 			.ok_or(WalletError::WalletSignIn)
 	}
 
+	/// Falls back to signing the sign in message with `solana:signMessage`
+	/// when the wallet doesn't support `solana:signIn`.
 	async fn sign_in_many(
 		&self,
 		inputs: Vec<SolanaSignInInput>,
@@ -133,9 +262,18 @@ impl WalletSolanaSignIn for BrowserWallet {
 			})
 			.collect();
 
-		self.wallet
-			.get_feature::<SolanaSignInFeature>()?
-			.sign_in(inputs)
-			.await
+		match self.wallet.get_feature::<SolanaSignInFeature>() {
+			Ok(feature) => {
+				let outputs = feature.sign_in(inputs).await?;
+				Ok(outputs
+					.into_iter()
+					.map(BrowserSignInOutput::SignIn)
+					.collect())
+			}
+			Err(WalletError::UnsupportedFeature { .. }) => {
+				self.sign_in_with_sign_message(inputs).await
+			}
+			Err(error) => Err(error),
+		}
 	}
 }
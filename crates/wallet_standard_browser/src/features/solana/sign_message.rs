@@ -12,6 +12,7 @@ use wallet_standard::SolanaSignatureOutput;
 use wallet_standard::WalletError;
 use wallet_standard::WalletResult;
 use wallet_standard::WalletSolanaSignMessage;
+use wallet_standard::expect_outputs;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
@@ -117,10 +118,14 @@ impl SolanaSignMessageFeature {
 		&self,
 		inputs: Vec<SolanaSignMessageInput>,
 	) -> WalletResult<Vec<BrowserSolanaSignMessageOutput>> {
+		let expected = inputs.len();
 		let array: Array = serde_wasm_bindgen::to_value(&inputs)?.unchecked_into();
 		let results: Array = self._sign_message(array).await?.dyn_into()?;
 
-		Ok(results.into_iter().map(JsCast::unchecked_into).collect())
+		expect_outputs(
+			expected,
+			results.into_iter().map(JsCast::unchecked_into).collect(),
+		)
 	}
 }
 
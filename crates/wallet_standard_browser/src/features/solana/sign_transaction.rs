@@ -15,6 +15,7 @@ use wallet_standard::SolanaSignTransactionPropsWithBytes;
 use wallet_standard::WalletError;
 use wallet_standard::WalletResult;
 use wallet_standard::WalletSolanaSignTransaction;
+use wallet_standard::expect_outputs;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
@@ -143,12 +144,15 @@ impl SolanaSignTransactionFeature {
 
 				Ok(input)
 			})
-			.collect::<WalletResult<Vec<_>>>();
+			.collect::<WalletResult<Vec<_>>>()?;
 
 		let js_inputs: Array = serde_wasm_bindgen::to_value(&inputs)?.dyn_into()?;
 		let js_results: Array = self._sign_transaction(js_inputs).await?.dyn_into()?;
 
-		Ok(js_results.into_iter().map(JsCast::unchecked_into).collect())
+		expect_outputs(
+			inputs.len(),
+			js_results.into_iter().map(JsCast::unchecked_into).collect(),
+		)
 	}
 }
 
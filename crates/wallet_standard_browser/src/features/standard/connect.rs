@@ -7,6 +7,7 @@ use wallet_standard::StandardConnectOutput;
 use wallet_standard::WalletError;
 use wallet_standard::WalletResult;
 use wallet_standard::WalletStandardConnect;
+use wallet_standard::reconcile_wallet_account;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
@@ -69,18 +70,28 @@ impl WalletStandardConnect for BrowserWallet {
 			.await
 	}
 
-	/// Connect the account and automatically update the attached account.
+	/// Connect the accounts and keep every authorized account. The active
+	/// account is preserved when it is still authorized, otherwise the first
+	/// account becomes active.
 	#[allow(clippy::manual_async_fn)]
 	async fn connect_with_options(
 		&mut self,
 		options: StandardConnectInput,
 	) -> WalletResult<Vec<Self::Account>> {
-		let accounts = self.connect_with_options(options).await?;
-		let account = accounts
-			.first()
-			.cloned()
-			.ok_or(WalletError::WalletConnection)?;
-		self.wallet_account = Some(account);
+		let accounts = self
+			.wallet
+			.get_feature::<StandardConnectFeature>()?
+			.connect_with_options(options)
+			.await?;
+
+		if accounts.is_empty() {
+			return Err(WalletError::WalletConnection);
+		}
+
+		// Keep the active account when it is still authorized.
+		let active = self.wallet_account.as_ref().or(accounts.first());
+		self.wallet_account = reconcile_wallet_account(active, &accounts);
+		self.accounts.clone_from(&accounts);
 
 		Ok(accounts)
 	}
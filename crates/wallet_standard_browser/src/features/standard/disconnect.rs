@@ -39,8 +39,12 @@ impl WalletStandardDisconnect for BrowserWallet {
 			return Err(WalletError::WalletDisconnected);
 		}
 
-		self.disconnect().await?;
+		self.wallet
+			.get_feature::<StandardDisconnectFeature>()?
+			.disconnect()
+			.await?;
 		self.wallet_account = None;
+		self.accounts.clear();
 
 		Ok(())
 	}
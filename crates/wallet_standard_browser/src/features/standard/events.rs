@@ -1,10 +1,15 @@
 #![allow(unsafe_code)]
 
+use futures::channel::mpsc;
 use js_sys::Function;
 use js_sys::Object;
 use wallet_standard::ConnectedWalletStandardEvents;
 use wallet_standard::STANDARD_EVENTS;
+use wallet_standard::STANDARD_EVENTS_CHANGE;
 use wallet_standard::StandardEventProperties;
+use wallet_standard::StandardEventsChange;
+use wallet_standard::WalletEventStream;
+use wallet_standard::WalletInfo;
 use wallet_standard::WalletResult;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::*;
@@ -75,8 +80,74 @@ impl StandardEventProperties for BrowserStandardEventsProperties {
 	}
 }
 
+impl From<&BrowserStandardEventsProperties> for StandardEventsChange<BrowserWalletAccountInfo> {
+	fn from(properties: &BrowserStandardEventsProperties) -> Self {
+		let features = properties.features().map(|features| {
+			Object::keys(&features)
+				.iter()
+				.filter_map(|key| key.as_string())
+				.collect()
+		});
+
+		Self {
+			chains: properties.chains(),
+			features,
+			accounts: properties.accounts(),
+		}
+	}
+}
+
 impl_feature_from_js!(StandardEventsFeature, STANDARD_EVENTS);
 
+/// Keeps the `change` listener of a [`BrowserWalletEventStream`] registered
+/// until the stream is dropped.
+pub struct BrowserEventsSubscription {
+	dispose: Box<dyn Fn()>,
+	_callback: Closure<dyn Fn(BrowserStandardEventsProperties)>,
+}
+
+impl Drop for BrowserEventsSubscription {
+	fn drop(&mut self) {
+		(self.dispose)();
+	}
+}
+
+/// A stream of the changes to a [`BrowserWallet`].
+pub type BrowserWalletEventStream =
+	WalletEventStream<BrowserWalletAccountInfo, BrowserEventsSubscription>;
+
+impl BrowserWallet {
+	/// A stream of typed [`wallet_standard::WalletEvent`]s, starting from the
+	/// currently authorized accounts.
+	///
+	/// ```rust,ignore
+	/// let mut events = wallet.event_stream()?;
+	///
+	/// while let Some(event) = events.next().await {
+	///     log::info!("{event:?}");
+	/// }
+	/// ```
+	pub fn event_stream(&self) -> WalletResult<BrowserWalletEventStream> {
+		let (sender, receiver) = mpsc::unbounded();
+		let callback = Closure::<dyn Fn(BrowserStandardEventsProperties)>::new(
+			move |properties: BrowserStandardEventsProperties| {
+				sender.unbounded_send((&properties).into()).ok();
+			},
+		);
+		let dispose = self.on(STANDARD_EVENTS_CHANGE, &callback)?;
+		let subscription = BrowserEventsSubscription {
+			dispose,
+			_callback: callback,
+		};
+
+		Ok(WalletEventStream::new(
+			receiver,
+			self.wallet.accounts(),
+			subscription,
+		))
+	}
+}
+
 impl ConnectedWalletStandardEvents for BrowserWallet {
 	type Callback = Closure<dyn Fn(BrowserStandardEventsProperties)>;
 
@@ -1,19 +1,31 @@
-#![doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"))]
+#![cfg_attr(
+	not(doctest),
+	doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"))
+)]
 #![allow(clippy::manual_async_fn)]
 
+pub use abort::*;
 pub use browser_wallet::*;
 pub use browser_wallet_info::*;
 pub use constants::*;
 pub use features::*;
+pub use semver;
 pub use types::*;
 pub use wallet_standard::*;
 
+mod abort;
 mod browser_wallet;
 mod browser_wallet_info;
 mod constants;
 mod features;
 mod types;
 
+/// The readme examples use the Solana features, so they are only tested with
+/// the `solana` feature enabled.
+#[cfg(all(doctest, feature = "solana"))]
+#[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/readme.md"))]
+struct ReadmeDoctests;
+
 pub mod prelude {
 	pub use wallet_standard::prelude::*;
 
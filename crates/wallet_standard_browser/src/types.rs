@@ -1,11 +1,21 @@
 use js_sys::Object;
 use js_sys::Reflect;
+use semver::Comparator;
+use semver::Op;
+use semver::Prerelease;
+use semver::Version;
+use semver::VersionReq;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 
 pub trait FeatureFromJs: JsCast + Clone + core::fmt::Debug {
 	/// The colon separated name of the feature in the JS object.
 	const NAME: &'static str;
+	/// The minimum version of the feature which these bindings support.
+	/// `BrowserWalletInfo::get_feature` fails for wallets which provide an
+	/// older version. Missing, non-semver and prerelease versions are
+	/// accepted.
+	const MIN_VERSION: Version = Version::new(1, 0, 0);
 
 	/// Get the wallet feature from the JS Object.
 	fn feature_from_js_object(object: &Object) -> Option<Self> {
@@ -19,6 +29,50 @@ pub trait FeatureFromJs: JsCast + Clone + core::fmt::Debug {
 	fn feature_from_js_value(value: &JsValue) -> Option<Self> {
 		Self::feature_from_js_object(value.dyn_ref()?)
 	}
+
+	/// The version requirement derived from `MIN_VERSION`.
+	fn version_requirement() -> VersionReq {
+		VersionReq {
+			comparators: vec![Comparator {
+				op: Op::GreaterEq,
+				major: Self::MIN_VERSION.major,
+				minor: Some(Self::MIN_VERSION.minor),
+				patch: Some(Self::MIN_VERSION.patch),
+				pre: Prerelease::EMPTY,
+			}],
+		}
+	}
+
+	/// The raw version string of the feature provided by the wallet.
+	fn feature_version_string(&self) -> Option<String> {
+		feature_version_string(self.as_ref())
+	}
+
+	/// The version of the feature provided by the wallet, if it is a valid
+	/// semver version.
+	fn feature_version(&self) -> Option<Version> {
+		Version::parse(&self.feature_version_string()?).ok()
+	}
+
+	/// Whether the wallet provides a valid version which is older than
+	/// `MIN_VERSION`. Prereleases of `MIN_VERSION` aren't considered older.
+	fn is_older_than_min_version(&self) -> bool {
+		self.feature_version().is_some_and(|version| {
+			(version.major, version.minor, version.patch)
+				< (
+					Self::MIN_VERSION.major,
+					Self::MIN_VERSION.minor,
+					Self::MIN_VERSION.patch,
+				)
+		})
+	}
+}
+
+/// Read the `version` property of a feature object.
+pub(crate) fn feature_version_string(feature: &JsValue) -> Option<String> {
+	Reflect::get(feature, &JsValue::from_str("version"))
+		.ok()?
+		.as_string()
 }
 
 macro_rules! impl_feature_from_js {
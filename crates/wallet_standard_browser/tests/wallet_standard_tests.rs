@@ -3,8 +3,11 @@
 use js_sys::Array;
 use js_sys::Object;
 use js_sys::Reflect;
+use wallet_standard_browser::AbortSignalListener;
 use wallet_standard_browser::BrowserWallet;
+use wallet_standard_browser::BrowserWalletAccountInfo;
 use wallet_standard_browser::BrowserWalletInfo;
+use wallet_standard_browser::CancelHandle;
 use wallet_standard_browser::prelude::*;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_test::*;
@@ -15,6 +18,7 @@ use web_sys::window;
 // Configure wasm_bindgen_test to run in a browser environment
 wasm_bindgen_test_configure!(run_in_browser);
 
+#[allow(dead_code)]
 struct TestWallet {
 	name: String,
 	version: String,
@@ -222,3 +226,228 @@ pub async fn test_wallet_features() {
 		);
 	}
 }
+
+#[wasm_bindgen_test]
+pub async fn test_feature_versions() {
+	let wallet_info: BrowserWalletInfo = create_mock_wallet().unchecked_into();
+
+	// The mock wallet provides version `1.0.0` of every feature.
+	assert!(
+		wallet_info
+			.get_feature::<wallet_standard_browser::StandardConnectFeature>()
+			.is_ok()
+	);
+
+	let requirement = semver::VersionReq::parse(">=2.0.0").unwrap();
+	let result = wallet_info
+		.get_feature_with_version::<wallet_standard_browser::StandardConnectFeature>(&requirement);
+	assert!(matches!(
+		result,
+		Err(WalletError::UnsupportedFeatureVersion { .. })
+	));
+
+	let capabilities = wallet_info.capabilities();
+	assert_eq!(capabilities.len(), 3);
+
+	for capability in capabilities {
+		assert_eq!(capability.raw_version.as_deref(), Some("1.0.0"));
+		assert_eq!(capability.version, Some(semver::Version::new(1, 0, 0)));
+	}
+
+	// Only valid versions older than the minimum are rejected.
+	let features = Reflect::get(&wallet_info, &JsValue::from_str("features")).unwrap();
+	let connect_feature = Reflect::get(&features, &JsValue::from_str("standard:connect")).unwrap();
+
+	for (version, supported) in [
+		(JsValue::from_str("1.0.0-beta"), true),
+		(JsValue::from_str("latest"), true),
+		(JsValue::UNDEFINED, true),
+		(JsValue::from_str("0.9.0"), false),
+	] {
+		Reflect::set(&connect_feature, &JsValue::from_str("version"), &version).unwrap();
+
+		assert_eq!(
+			wallet_info
+				.get_feature::<wallet_standard_browser::StandardConnectFeature>()
+				.is_ok(),
+			supported
+		);
+		assert_eq!(
+			wallet_info.is_feature_supported::<wallet_standard_browser::StandardConnectFeature>(),
+			supported
+		);
+	}
+}
+
+fn create_mock_account(address: &str) -> BrowserWalletAccountInfo {
+	let account = Object::new();
+	Reflect::set(
+		&account,
+		&JsValue::from_str("address"),
+		&JsValue::from_str(address),
+	)
+	.unwrap();
+	Reflect::set(&account, &JsValue::from_str("publicKey"), &Array::new()).unwrap();
+	Reflect::set(&account, &JsValue::from_str("chains"), &Array::new()).unwrap();
+	Reflect::set(&account, &JsValue::from_str("features"), &Array::new()).unwrap();
+
+	account.unchecked_into()
+}
+
+#[wasm_bindgen_test]
+pub async fn test_multiple_accounts() {
+	let wallet_js = create_mock_wallet();
+	let first = create_mock_account("first");
+	let second = create_mock_account("second");
+	let accounts = Array::of2(&first, &second);
+	Reflect::set(&wallet_js, &JsValue::from_str("accounts"), &accounts).unwrap();
+
+	let mut wallet = BrowserWallet::builder()
+		.wallet(wallet_js.unchecked_into::<BrowserWalletInfo>())
+		.wallet_account(first.clone())
+		.accounts(vec![first.clone(), second.clone()])
+		.build();
+
+	assert_eq!(wallet.wallet_accounts().len(), 2);
+	assert!(wallet.select_account("unknown").is_err());
+
+	let other = wallet.with_account("second").unwrap();
+	assert_eq!(other.wallet_account(), Some(second.clone()));
+	assert_eq!(wallet.wallet_account(), Some(first.clone()));
+
+	wallet.select_account("second").unwrap();
+	assert_eq!(wallet.wallet_account(), Some(second.clone()));
+
+	// The active account is no longer authorized.
+	accounts.pop();
+	assert_eq!(wallet.sync_wallet_account(), Some(first.clone()));
+	assert_eq!(wallet.wallet_accounts(), vec![first]);
+
+	accounts.pop();
+	assert_eq!(wallet.sync_wallet_account(), None);
+	assert!(!wallet.connected());
+}
+
+#[wasm_bindgen_test]
+pub fn test_js_error_codes() {
+	let rejected = Object::new();
+	Reflect::set(&rejected, &JsValue::from_str("code"), &JsValue::from(4001)).unwrap();
+	let WalletError::Js(error) = WalletError::from(JsValue::from(rejected)) else {
+		panic!("expected a js error");
+	};
+	assert_eq!(error.kind(), Some(WalletError::UserRejected));
+	assert_eq!(error.kind().map(|kind| kind.code()), Some(4001));
+	assert!(WalletError::Js(error).is_user_rejected());
+
+	let timeout = js_sys::Error::new("timed out");
+	timeout.set_name("WalletTimeoutError");
+	let WalletError::Js(error) = WalletError::from(JsValue::from(timeout)) else {
+		panic!("expected a js error");
+	};
+	assert_eq!(error.kind(), Some(WalletError::WalletTimeout));
+	assert_eq!(error.message, "timed out");
+}
+
+#[wasm_bindgen_test]
+pub fn test_js_error_details() {
+	let cause = js_sys::Error::new("Blockhash not found");
+	let thrown = js_sys::Error::new("Transaction simulation failed");
+	thrown.set_name("WalletSendTransactionError");
+	thrown.set_cause(&cause);
+	Reflect::set(&thrown, &JsValue::from_str("code"), &JsValue::from(-32002)).unwrap();
+
+	let WalletError::Js(error) = WalletError::from(JsValue::from(thrown)) else {
+		panic!("expected a js error");
+	};
+
+	assert_eq!(error.kind(), Some(WalletError::WalletSendTransaction));
+	assert_eq!(error.name.as_deref(), Some("WalletSendTransactionError"));
+	assert_eq!(error.message, "Transaction simulation failed");
+	assert_eq!(error.code, Some(-32002));
+	assert!(error.stack.is_some());
+	assert_eq!(
+		error.cause.map(|cause| cause.message),
+		Some("Blockhash not found".to_string())
+	);
+	assert_eq!(
+		WalletError::from(JsValue::from_str("plain")).to_string(),
+		"plain"
+	);
+}
+
+#[wasm_bindgen_test]
+pub fn test_abort_signal_cancels_the_handle() {
+	let controller = web_sys::AbortController::new().unwrap();
+	let handle = CancelHandle::new();
+	let listener = AbortSignalListener::new(&controller.signal(), handle.clone()).unwrap();
+
+	assert!(!handle.is_cancelled());
+	controller.abort();
+	assert!(handle.is_cancelled());
+	drop(listener);
+
+	let aborted = web_sys::AbortController::new().unwrap();
+	aborted.abort();
+	let handle = CancelHandle::new();
+	let _listener = AbortSignalListener::new(&aborted.signal(), handle.clone()).unwrap();
+	assert!(handle.is_cancelled());
+}
+
+#[cfg(feature = "solana")]
+#[wasm_bindgen_test]
+pub async fn test_sign_in_falls_back_to_sign_message() {
+	use wallet_standard::SolanaSignInInput;
+	use wallet_standard::create_sign_in_message_text;
+
+	let address = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+	let wallet_js = create_mock_wallet();
+	let features = Reflect::get(&wallet_js, &JsValue::from_str("features")).unwrap();
+
+	// The mock wallet supports `solana:signMessage` but not `solana:signIn`.
+	let connect = Reflect::get(&features, &JsValue::from_str("standard:connect")).unwrap();
+	Reflect::set(
+		&connect,
+		&JsValue::from_str("account"),
+		&create_mock_account(address),
+	)
+	.unwrap();
+	Reflect::set(
+		&connect,
+		&JsValue::from_str("connect"),
+		&js_sys::Function::new_no_args("return Promise.resolve({ accounts: [this.account] });"),
+	)
+	.unwrap();
+	let sign_message = Reflect::get(&features, &JsValue::from_str("solana:signMessage")).unwrap();
+	Reflect::set(
+		&sign_message,
+		&JsValue::from_str("signMessage"),
+		&js_sys::Function::new_no_args(
+			"return Promise.resolve(Array.from(arguments).map((input) => ({ signedMessage: \
+			 input.message, signature: new Uint8Array(64) })));",
+		),
+	)
+	.unwrap();
+
+	let mut wallet = BrowserWallet::from(wallet_js.unchecked_into::<BrowserWalletInfo>());
+	let input = SolanaSignInInput::builder()
+		.domain("example.com")
+		.nonce("abcdef12")
+		.build();
+
+	// Signing in needs a connected account.
+	assert_eq!(
+		wallet.sign_in(input.clone()).await.err(),
+		Some(WalletError::WalletAccount)
+	);
+
+	let output = wallet.connect_and_sign_in(input.clone()).await.unwrap();
+	let expected = create_sign_in_message_text(&SolanaSignInInput {
+		address: Some(address.into()),
+		..input
+	})
+	.unwrap();
+
+	assert!(wallet.connected());
+	assert_eq!(output.account().address(), address);
+	assert_eq!(output.signed_message(), expected.into_bytes());
+}